@@ -0,0 +1,92 @@
+//! The items the code generated by `#[derive(Views)]` refers to, re-exported
+//! by `viu`, since the proc-macro crate `viu-derive` can't export them.
+#![no_std]
+
+/// Implemented by every view generated by `#[derive(Views)]`, including the
+/// shared companions `FooRef`.
+pub trait View {
+    /// The struct the view borrows its fields from.
+    type Source: ?Sized;
+
+    /// The name of the view.
+    const NAME: &'static str;
+}
+
+/// Reaches a field of `S` of type `T`, generated by `#[view_lens]` for a
+/// `mut_in` field. It holds function pointers rather than closures borrowing
+/// the field, so it borrows `S` only for each call and can be kept around.
+pub struct Lens<S: ?Sized, T: ?Sized> {
+    get: fn(&S) -> &T,
+    get_mut: fn(&mut S) -> &mut T,
+}
+
+impl<S: ?Sized, T: ?Sized> Lens<S, T> {
+    /// The lens reaching the field through `get` and `get_mut`.
+    pub const fn new(get: fn(&S) -> &T, get_mut: fn(&mut S) -> &mut T) -> Self {
+        Self { get, get_mut }
+    }
+
+    /// Borrows the field of `source`.
+    #[inline]
+    pub fn get<'s>(&self, source: &'s S) -> &'s T {
+        (self.get)(source)
+    }
+
+    /// Borrows the field of `source` mutably.
+    #[inline]
+    pub fn get_mut<'s>(&self, source: &'s mut S) -> &'s mut T {
+        (self.get_mut)(source)
+    }
+
+    /// Sets the field of `source` to `value`.
+    #[inline]
+    pub fn set(&self, source: &mut S, value: T)
+    where
+        T: Sized,
+    {
+        *(self.get_mut)(source) = value;
+    }
+
+    /// The lens only reading the field.
+    #[inline]
+    pub fn getter(&self) -> Getter<S, T> {
+        Getter::new(self.get)
+    }
+}
+
+impl<S: ?Sized, T: ?Sized> Clone for Lens<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ?Sized, T: ?Sized> Copy for Lens<S, T> {}
+
+/// Reads a field of `S` of type `T`, generated by `#[view_lens]` for a field
+/// the view doesn't borrow mutably.
+pub struct Getter<S: ?Sized, T: ?Sized> {
+    get: fn(&S) -> &T,
+}
+
+impl<S: ?Sized, T: ?Sized> Getter<S, T> {
+    /// The lens reading the field through `get`.
+    pub const fn new(get: fn(&S) -> &T) -> Self {
+        Self { get }
+    }
+
+    /// Borrows the field of `source`.
+    #[inline]
+    pub fn get<'s>(&self, source: &'s S) -> &'s T {
+        (self.get)(source)
+    }
+}
+
+impl<S: ?Sized, T: ?Sized> Clone for Getter<S, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ?Sized, T: ?Sized> Copy for Getter<S, T> {}
@@ -0,0 +1,5323 @@
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::iter::FromIterator;
+use syn::ext::IdentExt;
+use syn::parenthesized;
+use syn::parse::{Parse, ParseStream};
+use syn::parse_macro_input;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::DeriveInput;
+use syn::Ident;
+use syn::Token;
+
+use crate::Sharable::{Cloned, Copied, Mut, Ref};
+use for_ch::for_ch;
+
+const VIEW_AS: &str = "view_as";
+const REF_IN: &str = "ref_in";
+const MUT_IN: &str = "mut_in";
+const VIEW_DERIVE: &str = "view_derive";
+const VIEW_DEBUG: &str = "view_debug";
+const VIEW_ALL_REF: &str = "view_all_ref";
+const VIEW_ALL_MUT: &str = "view_all_mut";
+const VIEW_SUBSET: &str = "view_subset";
+const VIEW_SPLIT: &str = "view_split";
+const VIEW_ALLOW_EMPTY: &str = "view_allow_empty";
+const PROJECT_IN: &str = "project_in";
+const REBORROW_IN: &str = "reborrow_in";
+const BORROW_IN: &str = "borrow_in";
+const VIEW_DOC: &str = "view_doc";
+const VIEW_MACRO: &str = "view_macro";
+const VIEWS: &str = "views";
+const COPY_IN: &str = "copy_in";
+const CLONE_IN: &str = "clone_in";
+const VIEW_VIS: &str = "view_vis";
+const VIEW_ACCESSORS: &str = "view_accessors";
+const VIEW_FN_CTOR: &str = "view_fn_ctor";
+const VIEW_REST_MUT: &str = "view_rest_mut";
+const VIEW_MUST_USE: &str = "view_must_use";
+const VIEW_NON_EXHAUSTIVE: &str = "view_non_exhaustive";
+const VIEWS_DISJOINT: &str = "views_disjoint";
+const VIEW_BUILDER: &str = "view_builder";
+const VIEW_MARKER: &str = "view_marker";
+const VIEW_PAIR: &str = "view_pair";
+const VIEWS_MODULE: &str = "views_module";
+const SPLIT_GROUP: &str = "split_group";
+const REF_SPLIT: &str = "ref_split";
+const VIEW_ASSERT: &str = "view_assert";
+const VIEW_REPR: &str = "view_repr";
+const VIEW_REBORROW: &str = "view_reborrow";
+const VIEW_INDEX: &str = "view_index";
+const VIEW_FORWARD_ATTRS: &str = "view_forward_attrs";
+const VIEW_BORROW_THROUGH: &str = "view_borrow_through";
+const VIEW_NARROW: &str = "view_narrow";
+const VIEW_WRAP: &str = "view_wrap";
+const VIEW_NAMING: &str = "view_naming";
+const VIEW_REFLECT: &str = "view_reflect";
+const VIEW_ALIAS: &str = "view_alias";
+const VIEW_DELEGATE: &str = "view_delegate";
+const VIEW_LENS: &str = "view_lens";
+
+/// The attributes viu reads on fields, never forwarded onto the views
+const FIELD_ATTRS: [&str; 8] = [
+    REF_IN,
+    MUT_IN,
+    COPY_IN,
+    CLONE_IN,
+    VIEWS,
+    PROJECT_IN,
+    REBORROW_IN,
+    BORROW_IN,
+];
+
+struct IdentTuple {
+    pub _paren_token: Option<syn::token::Paren>,
+    pub elems: Punctuated<Ident, Token![,]>,
+}
+
+/// `(Foo, Bar(ref: a, b; mut: c))`, the views, optionally along with their fields
+struct ViewDecls {
+    pub elems: Punctuated<ViewDecl, Token![,]>,
+}
+
+struct ViewDecl {
+    pub view: Ident,
+    pub fields: Option<Vec<(Sharable, syn::Member)>>,
+}
+
+/// `Foo` or `Foo as name`, a view of a field, optionally under another name, and `Foo as &mut [u8]`
+/// or `Foo as name: &mut [u8]` with another type the field coerces to
+struct FieldView {
+    pub view: Ident,
+    pub rename: Option<Ident>,
+    pub retype: Option<syn::TypeReference>,
+    /// whether the view gets an accessor to the content of the `Option` field
+    pub transpose: bool,
+}
+
+/// `(Foo, Bar as name)`, or `(Foo, transpose)` for an `Option` field
+struct FieldViewTuple {
+    pub elems: Vec<FieldView>,
+    pub transpose: Option<Ident>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Sharable {
+    Ref,
+    Mut,
+    /// held by value, for `Copy` fields
+    Copied,
+    /// held by value, cloned out of the struct
+    Cloned,
+}
+
+impl Sharable {
+    fn attr_name(self) -> &'static str {
+        match self {
+            Ref => REF_IN,
+            Mut => MUT_IN,
+            Copied => COPY_IN,
+            Cloned => CLONE_IN,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ViewField {
+    /// how the field is accessed on the original struct
+    member: syn::Member,
+    /// the field name used in the view
+    ident: Ident,
+    vis: syn::Visibility,
+    /// the visibility of the accessor methods, if the field is private behind them
+    accessor_vis: Option<syn::Visibility>,
+    /// the `#[doc]` attributes of the original field
+    docs: Vec<syn::Attribute>,
+    /// the `#[cfg]` attributes of the original field, put on everything generated for the field
+    cfgs: Vec<syn::Attribute>,
+    /// the other attributes of the original field, put on the view field by `view_forward_attrs`
+    forwarded: Vec<syn::Attribute>,
+    share: Sharable,
+    /// the type borrowed by the view, the referent when `deref`
+    ty: syn::Type,
+    /// whether the original field is a reference that the view reborrows through
+    deref: bool,
+    /// the views of the field type to project the field into
+    projections: Vec<syn::Path>,
+    /// the content of an `Option` field to give an accessor to, by `transpose`
+    transposed: Option<syn::Type>,
+    /// the type wrapping the borrow of a `mut_in` field, by `view_wrap`
+    wrap: Option<syn::Path>,
+    /// the content of a `RefCell` field to give a `borrow_mut` accessor to, by `borrow_in`
+    cell: Option<syn::Type>,
+}
+
+/// `(Foo: Debug, Clone)` targets the view `Foo`, `(Debug, Clone)` targets all the views.
+struct ViewDerive {
+    pub view: Option<Ident>,
+    pub derives: Punctuated<syn::Path, Token![,]>,
+}
+
+impl Parse for ViewDerive {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let view = if content.peek(Ident) && content.peek2(Token![:]) && !content.peek2(Token![::])
+        {
+            let view = content.parse()?;
+            content.parse::<Token![:]>()?;
+            Some(view)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            view,
+            derives: Punctuated::<syn::Path, Token![,]>::parse_terminated(&content)?,
+        })
+    }
+}
+
+/// `(Small: from Big)`, or `(Big => Small)` for `view_narrow`
+struct ViewSubset {
+    pub small: Ident,
+    pub big: Ident,
+    /// whether the narrowing also goes by `From`, as `view_narrow` does
+    pub narrow: bool,
+}
+
+impl Parse for ViewSubset {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let small = content.parse()?;
+        content.parse::<Token![:]>()?;
+        let from = content.parse::<Ident>()?;
+        if from != "from" {
+            return Err(syn::Error::new(from.span(), "expected `from`"));
+        }
+        let big = content.parse()?;
+        content.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            small,
+            big,
+            narrow: false,
+        })
+    }
+}
+
+/// `(Big => Small)`
+struct ViewNarrow(ViewSubset);
+
+impl Parse for ViewNarrow {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let big = content.parse()?;
+        content.parse::<Token![=>]>()?;
+        let small = content.parse()?;
+        content.parse::<Option<Token![,]>>()?;
+
+        Ok(Self(ViewSubset {
+            small,
+            big,
+            narrow: true,
+        }))
+    }
+}
+
+/// `(V)` or `(V, except(a, b))`
+struct ViewRest {
+    pub view: Ident,
+    pub except: Punctuated<syn::Member, Token![,]>,
+}
+
+impl Parse for ViewRest {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let view = content.parse()?;
+        let mut except = Punctuated::new();
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+        if !content.is_empty() {
+            let keyword = content.parse::<Ident>()?;
+            if keyword != "except" {
+                return Err(syn::Error::new(keyword.span(), "expected `except`"));
+            }
+            let members;
+            parenthesized!(members in content);
+            except = Punctuated::parse_terminated(&members)?;
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Self { view, except })
+    }
+}
+
+/// `(V -> InnerSub, W -> inner::Other)`
+struct ProjectIn {
+    pub elems: Punctuated<Projection, Token![,]>,
+}
+
+struct Projection {
+    pub view: Ident,
+    pub target: syn::Path,
+}
+
+impl Parse for ProjectIn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+impl Parse for Projection {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let view = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let target = input.parse()?;
+
+        Ok(Self { view, target })
+    }
+}
+
+/// `(Foo = "docs of Foo", Bar = "docs of Bar")`
+struct ViewDoc {
+    pub elems: Punctuated<(Ident, syn::LitStr), Token![,]>,
+}
+
+impl Parse for ViewDoc {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Ok((view, input.parse()?))
+            })?,
+        })
+    }
+}
+
+/// `(ref: A, B; mut: C)`
+struct FieldViews {
+    pub elems: Vec<(Sharable, FieldView)>,
+}
+
+impl Parse for FieldViews {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: parse_sharable_lists(&content, true)?,
+        })
+    }
+}
+
+impl Parse for ViewDecls {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                let fields = if input.peek(syn::token::Paren) {
+                    let fields;
+                    parenthesized!(fields in input);
+                    Some(parse_sharable_lists(&fields, true)?)
+                } else {
+                    None
+                };
+                Ok(ViewDecl { view, fields })
+            })?,
+        })
+    }
+}
+
+/// `ref: a, b; mut: c`, or `ref a, b; mut c` without `colon`
+fn parse_sharable_lists<T: Parse>(
+    content: ParseStream,
+    colon: bool,
+) -> syn::Result<Vec<(Sharable, T)>> {
+    let mut elems = Vec::new();
+    while !content.is_empty() {
+        let lookahead = content.lookahead1();
+        let share = if lookahead.peek(Token![ref]) {
+            content.parse::<Token![ref]>()?;
+            Ref
+        } else if lookahead.peek(Token![mut]) {
+            content.parse::<Token![mut]>()?;
+            Mut
+        } else {
+            return Err(lookahead.error());
+        };
+        if colon {
+            content.parse::<Token![:]>()?;
+        }
+
+        loop {
+            elems.push((share, content.parse()?));
+            // a trailing comma ends the list as well
+            let comma = content.parse::<Option<Token![,]>>()?;
+            if content.is_empty() {
+                break;
+            }
+            if content.peek(Token![;]) {
+                content.parse::<Token![;]>()?;
+                break;
+            }
+            if comma.is_none() {
+                return Err(content.error("expected `,` or `;`"));
+            }
+        }
+    }
+
+    Ok(elems)
+}
+
+/// `(Foo = pub(crate), Bar = priv)`
+struct ViewVis {
+    pub elems: Punctuated<(Ident, syn::Visibility), Token![,]>,
+}
+
+impl Parse for ViewVis {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let vis = if input.peek(Token![priv]) {
+                    input.parse::<Token![priv]>()?;
+                    syn::Visibility::Inherited
+                } else {
+                    input.parse()?
+                };
+                Ok((view, vis))
+            })?,
+        })
+    }
+}
+
+/// `Foo` or `Foo = FooRead`, a view with its read-only companion
+struct ViewPair {
+    pub view: Ident,
+    pub companion: Option<Ident>,
+}
+
+/// `(Foo, Bar = BarRead)`
+struct ViewPairs {
+    pub elems: Punctuated<ViewPair, Token![,]>,
+}
+
+impl Parse for ViewPairs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                let companion = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                Ok(ViewPair { view, companion })
+            })?,
+        })
+    }
+}
+
+/// `(Foo = Locked, Bar = *const ())`
+struct ViewMarker {
+    pub elems: Punctuated<(Ident, syn::Type), Token![,]>,
+}
+
+/// `(prefix = "My", suffix = "View")`
+struct ViewNaming {
+    pub prefix: Option<syn::LitStr>,
+    pub suffix: Option<syn::LitStr>,
+}
+
+impl Parse for ViewNaming {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let mut naming = Self {
+            prefix: None,
+            suffix: None,
+        };
+        for (key, value) in Punctuated::<_, Token![,]>::parse_terminated_with(&content, |input| {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            Ok((key, input.parse::<syn::LitStr>()?))
+        })? {
+            let slot = match key.to_string().as_str() {
+                "prefix" => &mut naming.prefix,
+                "suffix" => &mut naming.suffix,
+                _ => return Err(syn::Error::new(key.span(), "expected `prefix` or `suffix`")),
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("`{key}` is set more than once"),
+                ));
+            }
+        }
+
+        Ok(naming)
+    }
+}
+
+/// `(Foo: fn tick(&mut self, dt: f32) -> u32, fn name(&self) -> &str)`
+struct ViewDelegate {
+    pub view: Ident,
+    pub sigs: Punctuated<syn::Signature, Token![,]>,
+}
+
+impl Parse for ViewDelegate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let view = content.parse()?;
+        content.parse::<Token![:]>()?;
+        Ok(Self {
+            view,
+            sigs: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+/// `(Foo = Tracked, Bar = my_crate::Logged)`
+struct ViewWrap {
+    pub elems: Punctuated<(Ident, syn::Path), Token![,]>,
+}
+
+impl Parse for ViewWrap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Ok((view, input.parse()?))
+            })?,
+        })
+    }
+}
+
+/// `(Foo = C, Bar = align(8))`
+struct ViewRepr {
+    pub elems: Punctuated<(Ident, syn::Meta), Token![,]>,
+}
+
+impl Parse for ViewRepr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Ok((view, input.parse()?))
+            })?,
+        })
+    }
+}
+
+/// `(Foo = FooIn, Bar<i32> = IntBarIn)`, the arguments binding the type and const parameters
+/// of the struct
+struct ViewAlias {
+    pub elems: Punctuated<(Ident, Option<syn::AngleBracketedGenericArguments>, Ident), Token![,]>,
+}
+
+impl Parse for ViewAlias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                let args = if input.peek(Token![<]) {
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                input.parse::<Token![=]>()?;
+                Ok((view, args, input.parse()?))
+            })?,
+        })
+    }
+}
+
+/// `(Foo = reborrow_foo, Bar = _)`, `_` leaving out the method
+struct ViewReborrow {
+    pub elems: Punctuated<(Ident, Option<Ident>), Token![,]>,
+}
+
+impl Parse for ViewReborrow {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let method = if input.peek(Token![_]) {
+                    input.parse::<Token![_]>()?;
+                    None
+                } else {
+                    Some(input.parse()?)
+                };
+                Ok((view, method))
+            })?,
+        })
+    }
+}
+
+impl Parse for ViewMarker {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            elems: Punctuated::parse_terminated_with(&content, |input| {
+                let view = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Ok((view, input.parse()?))
+            })?,
+        })
+    }
+}
+
+/// `(G: a, b, 0)`
+struct SplitGroup {
+    pub group: Ident,
+    pub members: Punctuated<syn::Member, Token![,]>,
+}
+
+impl Parse for SplitGroup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let group = content.parse()?;
+        content.parse::<Token![:]>()?;
+        let members = Punctuated::parse_terminated(&content)?;
+
+        Ok(Self { group, members })
+    }
+}
+
+impl Parse for FieldView {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let view = input.parse()?;
+        let mut rename = None;
+        let mut retype = None;
+        if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            if !input.peek(Token![&]) {
+                rename = Some(input.parse()?);
+            }
+            if rename.is_none() || input.peek(Token![:]) {
+                if rename.is_some() {
+                    input.parse::<Token![:]>()?;
+                }
+                retype = Some(input.parse()?);
+            }
+        }
+
+        Ok(Self {
+            view,
+            rename,
+            retype,
+            transpose: false,
+        })
+    }
+}
+
+impl Parse for FieldViewTuple {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        let mut elems = Vec::new();
+        let mut transpose = None;
+        for elem in Punctuated::<FieldView, Token![,]>::parse_terminated(&content)? {
+            match elem {
+                FieldView {
+                    view, rename: None, ..
+                } if view == "transpose" => transpose = Some(view),
+                elem => elems.push(elem),
+            }
+        }
+        for elem in &mut elems {
+            elem.transpose = transpose.is_some();
+        }
+
+        Ok(Self { elems, transpose })
+    }
+}
+
+/// `#[view_derive(Debug)] Foreign<T> where T: Clone => pub Foo { ref a: A, b: B; mut c: C }`
+struct ForeignView {
+    pub attrs: Vec<syn::Attribute>,
+    pub struct_name: Ident,
+    pub generics: syn::Generics,
+    pub vis: syn::Visibility,
+    pub view: Ident,
+    pub fields: Vec<(Sharable, ForeignField)>,
+}
+
+/// `a: A`
+struct ForeignField {
+    pub ident: Ident,
+    pub ty: syn::Type,
+}
+
+impl Parse for ForeignView {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let struct_name = input.parse()?;
+        let mut generics = input.parse::<syn::Generics>()?;
+        generics.where_clause = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let vis = input.parse()?;
+        let view = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+
+        Ok(Self {
+            attrs,
+            struct_name,
+            generics,
+            vis,
+            view,
+            fields: parse_sharable_lists(&content, false)?,
+        })
+    }
+}
+
+impl Parse for ForeignField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+
+        Ok(Self {
+            ident,
+            ty: input.parse()?,
+        })
+    }
+}
+
+/// `(views)` or `(crate::path::to::views)`, a module by its name or by its path
+struct ModulePath(syn::Path);
+
+impl Parse for ModulePath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self(content.call(syn::Path::parse_mod_style)?))
+    }
+}
+
+impl Parse for IdentTuple {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self {
+                _paren_token: None,
+                elems: Default::default(),
+            });
+        }
+
+        let content;
+        let par = parenthesized!(content in input);
+
+        Ok(Self {
+            _paren_token: Some(par),
+            elems: Punctuated::<Ident, Token![,]>::parse_terminated(&content)?,
+        })
+    }
+}
+
+#[proc_macro_derive(
+    Views,
+    attributes(
+        view_as,
+        mut_in,
+        ref_in,
+        view_derive,
+        view_debug,
+        view_all_ref,
+        view_all_mut,
+        view_subset,
+        view_split,
+        view_allow_empty,
+        project_in,
+        view_doc,
+        view_macro,
+        views,
+        reborrow_in,
+        copy_in,
+        view_vis,
+        view_accessors,
+        view_fn_ctor,
+        view_rest_mut,
+        view_must_use,
+        view_non_exhaustive,
+        views_disjoint,
+        view_builder,
+        view_marker,
+        view_pair,
+        views_module,
+        split_group,
+        view_assert,
+        view_repr,
+        view_reborrow,
+        view_index,
+        clone_in,
+        view_forward_attrs,
+        view_borrow_through,
+        view_narrow,
+        view_wrap,
+        view_naming,
+        view_reflect,
+        view_alias,
+        borrow_in,
+        view_delegate,
+        ref_split,
+        view_lens
+    )
+)]
+pub fn views_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let deriving = parse_macro_input!(input as DeriveInput);
+    views_derive_impl(deriving, false)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Generates a view of a struct which can't derive `Views`, like one of another
+/// crate, out of its fields listed along with their types:
+///
+/// ```ignore
+/// viu::view! {
+///     #[view_derive(Debug)]
+///     Player => pub PosView { mut pos: (f32, f32); ref speed: f32 }
+/// }
+/// ```
+///
+/// The fields must be visible, the view is borrowed through the trait `AsPosView`.
+#[proc_macro]
+pub fn view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let foreign = parse_macro_input!(input as ForeignView);
+    foreign_view_impl(foreign)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives the view out of a struct made of the listed fields, without
+/// the methods the foreign struct can't have.
+fn foreign_view_impl(foreign: ForeignView) -> syn::Result<TokenStream> {
+    let ForeignView {
+        attrs,
+        struct_name,
+        generics,
+        vis,
+        view,
+        fields,
+    } = foreign;
+    let where_clause = &generics.where_clause;
+    let fields = fields.iter().map(|(share, ForeignField { ident, ty })| {
+        let attr_name = Ident::new(share.attr_name(), ident.span());
+        quote::quote!(#[#attr_name(#view)] #vis #ident: #ty)
+    });
+
+    views_derive_impl(
+        syn::parse_quote! {
+            #(#attrs)*
+            #[view_as(#view)]
+            #vis struct #struct_name #generics #where_clause {
+                #(#fields,)*
+            }
+        },
+        true,
+    )
+}
+
+/// Spells out the struct where it names itself `Self`, like in `next: Option<Box<Self>>` or
+/// `where T: Node<Self>`, since `Self` would name the view where its fields and bounds are copied.
+fn replace_self(input: syn::DeriveInput) -> syn::Result<syn::DeriveInput> {
+    fn walk(tokens: TokenStream, self_ty: &TokenStream) -> TokenStream {
+        let mut tokens = tokens.into_iter().peekable();
+        let mut replaced = TokenStream::new();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Group(group) => {
+                    let mut new_group =
+                        proc_macro2::Group::new(group.delimiter(), walk(group.stream(), self_ty));
+                    new_group.set_span(group.span());
+                    replaced.extend([TokenTree::Group(new_group)]);
+                }
+                // `Self::Item` becomes `<Foo<T>>::Item`, as `Foo<T>::Item` isn't a type
+                TokenTree::Ident(ident) if ident == "Self" => match tokens.peek() {
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {
+                        replaced.extend(quote::quote_spanned!(ident.span()=> <#self_ty>));
+                    }
+                    _ => replaced.extend(quote::quote_spanned!(ident.span()=> #self_ty)),
+                },
+                token => replaced.extend([token]),
+            }
+        }
+        replaced
+    }
+
+    fn mentions_self(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Group(group) => mentions_self(group.stream()),
+            TokenTree::Ident(ident) => ident == "Self",
+            _ => false,
+        })
+    }
+
+    let tokens = quote::quote!(#input);
+    if !mentions_self(tokens.clone()) {
+        return Ok(input);
+    }
+    let ident = &input.ident;
+    let gens = elide_generics_bounds(&Vec::from_iter(input.generics.params.iter().cloned()));
+    let self_ty = quote::quote!(#ident < #(#gens,)* >);
+    syn::parse2(walk(tokens, &self_ty))
+}
+
+/// Derives the views of `input`, or of a struct of another crate for `foreign`, which can only
+/// be extended by traits.
+fn views_derive_impl(input: syn::DeriveInput, foreign: bool) -> syn::Result<TokenStream> {
+    let input = replace_self(input)?;
+    if foreign {
+        for_ch! {
+            for attr in &input.attrs;
+            for attr_name in [VIEW_SPLIT, SPLIT_GROUP, REF_SPLIT, VIEW_PAIR, VIEW_DELEGATE];
+            if attr.path.is_ident(&Ident::new(attr_name, Span::call_site()));
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("`{attr_name}` adds methods to the struct, it can't be used in `view!`"),
+            ));
+        }
+    }
+
+    if let syn::Data::Enum(syn::DataEnum { variants, .. }) = &input.data {
+        return enum_views_derive_impl(&input, variants);
+    }
+
+    let gens_with_bounds = Vec::from_iter(input.generics.params);
+    let impl_gens = strip_generics_defaults(&gens_with_bounds);
+    let gens = elide_generics_bounds(&gens_with_bounds);
+    let (mut fields, is_union) = guard_struct(&input.ident, input.data)?;
+
+    // the checks are run by stages, each reporting all its errors at once, but only once the
+    // previous stages passed, as their errors would lead to bogus ones in the next stages
+    let mut errors = Errors::default();
+    let mut view_type_names = errors.take(view_type_names_from_attrs(&input.attrs));
+    let whole_views = errors.take(whole_views_from_attrs(&input.attrs, &mut view_type_names));
+    let rest_views = errors.take(rest_views_from_attrs(
+        &input.attrs,
+        &mut view_type_names,
+        &fields,
+    ));
+    errors.take(expand_bare_field_attrs(&mut fields, &view_type_names));
+    errors.finish()?;
+
+    let mut errors = Errors::default();
+    errors.take(check_view_references(
+        &view_type_names,
+        &whole_views,
+        &fields,
+    ));
+    let declared_fields = errors.take(declared_view_fields_from_attrs(&input.attrs, &fields));
+    let mut view_derives = errors.take(view_traits_from_attrs(
+        &input.attrs,
+        VIEW_DERIVE,
+        &view_type_names,
+    ));
+    let mut view_asserts = errors.take(view_traits_from_attrs(
+        &input.attrs,
+        VIEW_ASSERT,
+        &view_type_names,
+    ));
+    let mut view_docs = errors.take(view_docs_from_attrs(&input.attrs, &view_type_names));
+    let mut view_vises = errors.take(view_vises_from_attrs(&input.attrs, &view_type_names));
+    let mut view_markers = errors.take(view_markers_from_attrs(&input.attrs, &view_type_names));
+    let mut view_reprs = errors.take(view_reprs_from_attrs(&input.attrs, &view_type_names));
+    let mut view_wraps = errors.take(view_wraps_from_attrs(&input.attrs, &view_type_names));
+    let mut view_reborrows = errors.take(view_reborrows_from_attrs(&input.attrs, &view_type_names));
+    let mut view_aliases = errors.take(view_aliases_from_attrs(&input.attrs, &view_type_names));
+    let mut view_delegates = errors.take(view_delegates_from_attrs(&input.attrs, &view_type_names));
+    let forwarded_attrs = errors.take(forwarded_attrs_from_attrs(&input.attrs, &view_type_names));
+    let view_pairs = errors.take(view_pairs_from_attrs(&input.attrs, &view_type_names));
+    let local_macros = errors.take(local_macros_from_attrs(&input.attrs));
+    let views_module = errors.take(views_module_from_attrs(&input.attrs));
+    let view_idents = errors.take(view_idents_from_attrs(&input.attrs, &view_type_names));
+    let selected = |errors: &mut Errors, attr_name| {
+        errors.take(views_selected_by_attrs(
+            &input.attrs,
+            attr_name,
+            &view_type_names,
+        ))
+    };
+    let debug_views = selected(&mut errors, VIEW_DEBUG);
+    let empty_views = selected(&mut errors, VIEW_ALLOW_EMPTY);
+    let accessor_views = selected(&mut errors, VIEW_ACCESSORS);
+    let fn_ctor_views = selected(&mut errors, VIEW_FN_CTOR);
+    let must_use_views = selected(&mut errors, VIEW_MUST_USE);
+    let non_exhaustive_views = selected(&mut errors, VIEW_NON_EXHAUSTIVE);
+    let index_views = selected(&mut errors, VIEW_INDEX);
+    let through_views = selected(&mut errors, VIEW_BORROW_THROUGH);
+    let reflect_views = selected(&mut errors, VIEW_REFLECT);
+    let builder_views = selected(&mut errors, VIEW_BUILDER);
+    let lens_views = selected(&mut errors, VIEW_LENS);
+    let view_subsets = errors.take(view_subsets_from_attrs(&input.attrs, &view_type_names));
+    let view_splits = errors.take(view_splits_from_attrs(&input.attrs, &view_type_names));
+    let disjoint_views = errors.take(disjoint_views_from_attrs(&input.attrs, &view_type_names));
+    let split_groups = errors.take(split_groups_from_attrs(&input.attrs, SPLIT_GROUP, &fields));
+    let ref_splits = errors.take(split_groups_from_attrs(&input.attrs, REF_SPLIT, &fields));
+    errors.finish()?;
+
+    // a view may not name the reference fields the struct's implied bounds come from, like
+    // `T: 'a` for `&'a [T]`, which `View::Source` needs, so its marker keeps a shared one
+    let implied_markers = fields
+        .iter()
+        .filter(|field| !field.attrs.iter().any(|attr| attr.path.is_ident("cfg")))
+        .filter_map(|field| match &field.ty {
+            syn::Type::Reference(syn::TypeReference {
+                lifetime: Some(lifetime),
+                elem,
+                ..
+            }) => Some(syn::parse_quote!(&#lifetime #elem)),
+            _ => None,
+        })
+        .collect::<Vec<syn::Type>>();
+
+    // the generated items are as visible from their module as they'd be next to the struct
+    let vis = match &views_module {
+        Some(_) => nested_vis(&input.vis),
+        None => input.vis.clone(),
+    };
+
+    // the views are generated in the order of their names, so that the expansion is the same
+    // from one build to another
+    let mut view_names = view_type_names.into_values().collect::<Vec<_>>();
+    view_names.sort();
+    let mut view_structs = BTreeMap::new();
+    let mut errors = Errors::default();
+    for view_name in view_names {
+        let whole = whole_views.get(&view_name.to_string()).copied();
+        let rest = rest_views.get(&view_name.to_string());
+        let declared = declared_fields.get(&view_name.to_string());
+        let through = through_views.contains(&view_name.to_string());
+        let Some(mut view_fields) = errors.ok(view_type_fields(
+            &view_name, whole, rest, declared, through, &fields,
+        )) else {
+            continue;
+        };
+        if let Some(vis) = view_vises.remove(&view_name.to_string()) {
+            for field in &mut view_fields {
+                field.vis = vis.clone();
+            }
+        }
+        if views_module.is_some() {
+            for field in &mut view_fields {
+                field.vis = nested_vis(&field.vis);
+            }
+        }
+        match forwarded_attrs.get(&view_name.to_string()) {
+            None => view_fields
+                .iter_mut()
+                .for_each(|field| field.forwarded.clear()),
+            Some(None) => {}
+            Some(Some(allowed)) => {
+                for field in &mut view_fields {
+                    field.forwarded.retain(|attr| {
+                        let idents = |path: &syn::Path| {
+                            path.segments
+                                .iter()
+                                .map(|seg| seg.ident.clone())
+                                .collect::<Vec<_>>()
+                        };
+                        allowed
+                            .iter()
+                            .any(|path| idents(path) == idents(&attr.path))
+                    });
+                }
+            }
+        }
+        if let Some(wrap) = view_wraps.remove(&view_name.to_string()) {
+            for field in view_fields.iter_mut().filter(|field| field.share == Mut) {
+                field.wrap = Some(wrap.clone());
+            }
+        }
+        if accessor_views.contains(&view_name.to_string()) {
+            for field in &mut view_fields {
+                let vis = std::mem::replace(&mut field.vis, syn::Visibility::Inherited);
+                field.accessor_vis = Some(vis);
+            }
+        }
+        if let Some(field) = view_fields
+            .iter()
+            .find(|field| is_union && field.share != Ref)
+        {
+            errors.push(syn::Error::new(
+                field.ident.span(),
+                format!(
+                    "field `{}` of a union can only be borrowed by `{REF_IN}`",
+                    field.ident
+                ),
+            ));
+        }
+        if view_fields.is_empty() && !empty_views.contains(&view_name.to_string()) {
+            errors.push(syn::Error::new(
+                view_name.span(),
+                format!(
+                    "view `{view_name}` has no fields, add `{REF_IN}({view_name})` or `{MUT_IN}({view_name})` to some fields, or `#[{VIEW_ALLOW_EMPTY}({view_name})]` if it's meant to be empty"
+                ),
+            ));
+        }
+        view_structs.insert(view_name, view_fields);
+    }
+    errors.finish()?;
+
+    let mut errors = Errors::default();
+    for group in &disjoint_views {
+        for_ch! {
+            for (i, left) in group.iter().enumerate();
+            for right in &group[i + 1..];
+            errors.take(check_disjoint_views(
+                (left, &view_structs[left]),
+                (right, &view_structs[right]),
+            ));
+        }
+    }
+
+    // the views are named by `view_naming` from here on, while the maps are keyed by their
+    // names in `view_as`
+    let named_structs = view_structs
+        .iter()
+        .map(|(name, fields)| (view_idents[&name.to_string()].clone(), fields.clone()))
+        .collect::<BTreeMap<_, _>>();
+    let mut result = TokenStream::new();
+    if !allows_deprecated(&input.attrs) {
+        result.extend(redundant_field_views(&fields));
+        result.extend(needless_mut_field_views(&fields));
+    }
+    for (declared_name, view_fields) in &view_structs {
+        let key = declared_name.to_string();
+        let view_name = &view_idents[&key];
+        let (eq_derives, derives) = view_derives
+            .remove(&key)
+            .unwrap_or_default()
+            .into_iter()
+            .partition::<Vec<_>, _>(|path| path.is_ident("PartialEq"));
+        let (default_derives, derives) = derives
+            .into_iter()
+            .partition::<Vec<_>, _>(|path| path.is_ident("Default"));
+        let (total_eq_derives, derives) = derives
+            .into_iter()
+            .partition::<Vec<_>, _>(|path| path.is_ident("Eq"));
+        let (hash_derives, derives) = derives
+            .into_iter()
+            .partition::<Vec<_>, _>(|path| path.is_ident("Hash"));
+        let with_debug = debug_views.contains(&key);
+        if let Some(debug) = derives
+            .iter()
+            .find(|path| with_debug && path.is_ident("Debug"))
+        {
+            errors.push(syn::Error::new_spanned(
+                debug,
+                format!("view `{view_name}` derives `Debug` but also has `{VIEW_DEBUG}`"),
+            ));
+        }
+        let docs = view_docs.remove(&key).unwrap_or_default();
+        let mut markers = view_markers.remove(&key).unwrap_or_default();
+        markers.extend(implied_markers.iter().cloned());
+        let non_exhaustive = non_exhaustive_views.contains(&key);
+        let reprs = view_reprs.remove(&key).unwrap_or_default();
+        let reborrow = view_reborrows
+            .remove(&key)
+            .unwrap_or_else(|| Some(Ident::new("reborrow", Span::call_site())));
+        if let Some(repr) = reprs
+            .iter()
+            .find(|repr| repr.path().is_ident("transparent") && view_fields.len() > 1)
+        {
+            errors.push(syn::Error::new_spanned(
+                repr,
+                format!("view `{view_name}` can't be `transparent`, it has more than one field"),
+            ));
+        }
+        let the_struct = construct_view_type(
+            view_name,
+            view_fields,
+            &docs,
+            &derives,
+            &markers,
+            &reprs,
+            (must_use_views.contains(&key), non_exhaustive),
+            &vis,
+            &gens_with_bounds,
+            &gens,
+            &input.generics.where_clause,
+        );
+
+        let the_impl = construct_view_type_impl(
+            view_name,
+            view_fields,
+            reborrow.as_ref(),
+            &impl_gens,
+            &gens,
+            &input.generics.where_clause,
+        );
+
+        // a foreign struct gets no inherent method, only the trait
+        let the_delegates = view_delegates.remove(&key).map(|sigs| {
+            errors.take(construct_view_delegates(
+                (&input.ident, is_union),
+                view_name,
+                view_fields,
+                &sigs,
+                &vis,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ))
+        });
+
+        let the_method = (!foreign).then(|| {
+            construct_view_type_method(
+                (&input.ident, is_union),
+                view_name,
+                view_fields,
+                &vis,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            )
+        });
+
+        // reading the fields of a union is unsafe, it only gets the `unsafe` `as_` methods
+        let the_from = (!is_union)
+            .then(|| {
+                construct_view_type_from(
+                    &input.ident,
+                    view_name,
+                    view_fields,
+                    &impl_gens,
+                    &gens,
+                    &input.generics.where_clause,
+                )
+            })
+            .flatten();
+
+        let the_trait = construct_view_type_trait(
+            (&input.ident, is_union),
+            view_name,
+            view_fields,
+            foreign,
+            &named_structs,
+            &vis,
+            &gens_with_bounds,
+            &gens,
+            &input.generics.where_clause,
+        );
+        let the_trait = errors.take(the_trait);
+
+        let the_ctor = construct_view_type_ctor(
+            view_name,
+            view_fields,
+            local_macros,
+            views_module.as_ref().map(|(_, path)| path),
+        );
+
+        let the_fn_ctor = fn_ctor_views.contains(&key).then(|| {
+            construct_view_type_fn_ctor(
+                (&input.ident, is_union),
+                view_name,
+                view_fields,
+                &vis,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            )
+        });
+
+        let the_view_impl = construct_view_trait_impl(
+            &input.ident,
+            view_name,
+            view_fields,
+            &impl_gens,
+            &gens,
+            &input.generics.where_clause,
+        );
+
+        let the_shared = construct_view_type_shared(
+            &input.ident,
+            view_name,
+            view_fields,
+            &named_structs,
+            view_pairs.get(&key),
+            (&markers, non_exhaustive),
+            reborrow.as_ref(),
+            &vis,
+            &gens_with_bounds,
+            &gens,
+            &input.generics.where_clause,
+        );
+        let the_shared = errors.take(the_shared);
+
+        result.extend(the_struct);
+        result.extend(the_view_impl);
+        result.extend(the_impl);
+        result.extend(the_method);
+        result.extend(the_delegates);
+        result.extend(the_from);
+        result.extend(the_trait);
+        result.extend(the_ctor);
+        result.extend(the_fn_ctor);
+        result.extend(the_shared);
+
+        if builder_views.contains(&key) {
+            result.extend(errors.take(construct_view_builder(
+                view_name,
+                view_fields,
+                &named_structs,
+                &vis,
+                &gens_with_bounds,
+                &gens,
+                &input.generics.where_clause,
+            )));
+        }
+
+        if lens_views.contains(&key) {
+            result.extend(errors.take(construct_view_lens(
+                (&input.ident, is_union),
+                view_name,
+                view_fields,
+                &named_structs,
+                &vis,
+                &gens_with_bounds,
+                &gens,
+                &input.generics.where_clause,
+            )));
+        }
+
+        if !eq_derives.is_empty() {
+            result.extend(construct_view_type_eq(
+                (&input.ident, is_union),
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ));
+        }
+
+        if !total_eq_derives.is_empty() {
+            result.extend(construct_view_type_total_eq(
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ));
+        }
+
+        if let Some(hash) = hash_derives.first() {
+            result.extend(errors.take(construct_view_type_hash(
+                hash,
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            )));
+        }
+
+        if let Some(default) = default_derives.first() {
+            result.extend(errors.take(construct_view_default(
+                default,
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            )));
+        }
+
+        for (args, alias) in view_aliases.remove(&key).unwrap_or_default() {
+            result.extend(errors.take(construct_view_alias(
+                view_name,
+                &alias,
+                args.as_ref(),
+                view_fields,
+                &vis,
+                &gens_with_bounds,
+                &gens,
+            )));
+        }
+
+        if reflect_views.contains(&key) {
+            result.extend(construct_view_reflect(
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ));
+        }
+
+        if index_views.contains(&key) {
+            result.extend(errors.take(construct_view_index(
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            )));
+        }
+
+        if with_debug {
+            result.extend(construct_view_type_debug(
+                view_name,
+                view_fields,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ));
+        }
+
+        if let Some(traits) = view_asserts.remove(&key) {
+            result.extend(construct_view_assert(
+                view_name,
+                view_fields,
+                &traits,
+                &impl_gens,
+                &gens,
+                &input.generics.where_clause,
+            ));
+        }
+    }
+
+    for ViewSubset { small, big, narrow } in view_subsets {
+        result.extend(errors.take(construct_view_subset(
+            (&view_idents[&small.to_string()], &view_structs[&small]),
+            (&view_idents[&big.to_string()], &view_structs[&big]),
+            narrow,
+            &vis,
+            &impl_gens,
+            &gens,
+            &input.generics.where_clause,
+        )));
+    }
+
+    for (left, right) in view_splits {
+        if is_union {
+            errors.push(syn::Error::new(
+                left.span(),
+                format!("`{VIEW_SPLIT}` is not supported on unions"),
+            ));
+            continue;
+        }
+        result.extend(errors.take(construct_view_split(
+            &input.ident,
+            (&view_idents[&left.to_string()], &view_structs[&left]),
+            (&view_idents[&right.to_string()], &view_structs[&right]),
+            &vis,
+            &impl_gens,
+            &gens,
+            &input.generics.where_clause,
+        )));
+    }
+
+    let split_groups = split_groups.into_iter().map(|group| (group, false));
+    for ((group, members), shared) in
+        split_groups.chain(ref_splits.into_iter().map(|group| (group, true)))
+    {
+        if is_union {
+            let attr_name = if shared { REF_SPLIT } else { SPLIT_GROUP };
+            errors.push(syn::Error::new(
+                group.span(),
+                format!("`{attr_name}` is not supported on unions"),
+            ));
+            continue;
+        }
+        result.extend(construct_split_group(
+            &input.ident,
+            (&group, shared),
+            &members,
+            &vis,
+            &impl_gens,
+            &gens,
+            &input.generics.where_clause,
+        ));
+    }
+
+    errors.finish()?;
+
+    if let Some((module, _)) = views_module {
+        let doc = format!("The views of `{}`.", input.ident);
+        let module_vis = &input.vis;
+        result = quote::quote! {
+            #[doc = #doc]
+            #module_vis mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #result
+            }
+        };
+    }
+
+    Ok(result)
+}
+
+/// The errors of independent checks, reported together.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// The value of `result`, if it isn't an error to keep.
+    fn ok<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    /// The value of `result`, or a default one once its error is kept.
+    fn take<T: Default>(&mut self, result: syn::Result<T>) -> T {
+        self.ok(result).unwrap_or_default()
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// Derives the views of an enum, each an enum of the same variants holding the fields each
+/// variant shares with it by `ref_in`, the only borrow supported on enums.
+fn enum_views_derive_impl(
+    input: &syn::DeriveInput,
+    variants: &Punctuated<syn::Variant, Token![,]>,
+) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    // every attribute viu reads on a struct is named `view_...`, but `split_group`
+    for_ch! {
+        for attr in &input.attrs;
+        if let Some(ident) = attr.path.get_ident();
+        let name = ident.to_string();
+        if name.starts_with("view") || name == SPLIT_GROUP;
+        if ![VIEW_AS, VIEW_DERIVE, VIEW_DOC].contains(&&*name);
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("`{name}` can't be used on an enum, its views only take `{VIEW_AS}`, `{VIEW_DERIVE}` and `{VIEW_DOC}`"),
+        ));
+    }
+    for_ch! {
+        for attr in &input.attrs;
+        if attr.path.is_ident(VIEW_AS);
+        if !attr.tokens.is_empty();
+        let decls = parse_parenthesized::<ViewDecls>(attr, "Name1, Name2, ...")?;
+        for ViewDecl { view, fields } in decls.elems;
+        if fields.is_some();
+        return Err(syn::Error::new(
+            view.span(),
+            format!("the fields of view `{view}` of an enum are put in it by `{REF_IN}({view})`"),
+        ));
+    }
+
+    let mut errors = Errors::default();
+    let view_type_names = errors.take(view_type_names_from_attrs(&input.attrs));
+    let mut variants = variants.clone();
+    for variant in &mut variants {
+        errors.take(expand_bare_field_attrs(
+            &mut variant.fields,
+            &view_type_names,
+        ));
+    }
+    errors.finish()?;
+
+    let mut errors = Errors::default();
+    let mut view_derives = errors.take(view_traits_from_attrs(
+        &input.attrs,
+        VIEW_DERIVE,
+        &view_type_names,
+    ));
+    let mut view_docs = errors.take(view_docs_from_attrs(&input.attrs, &view_type_names));
+    // the views sharing each field, by variant
+    let mut field_views = Vec::<Vec<HashSet<String>>>::new();
+    for variant in &variants {
+        let mut variant_views = Vec::new();
+        for field in &variant.fields {
+            let mut views = HashSet::new();
+            for attr in &field.attrs {
+                if attr.path.is_ident(REF_IN) {
+                    let Some(idents) =
+                        errors.ok(parse_parenthesized::<IdentTuple>(attr, "View1, View2, ..."))
+                    else {
+                        continue;
+                    };
+                    for ident in idents.elems {
+                        if !view_type_names.contains_key(&ident.to_string()) {
+                            errors.push(undeclared_view_error(&ident, &view_type_names));
+                        } else if !views.insert(ident.to_string()) {
+                            errors.push(syn::Error::new(
+                                ident.span(),
+                                format!("the field is put in view `{ident}` more than once"),
+                            ));
+                        }
+                    }
+                } else if let Some(name) = FIELD_ATTRS.iter().find(|name| attr.path.is_ident(name))
+                {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        format!("`{name}` can't be used on the fields of an enum, they can only be shared by `{REF_IN}`"),
+                    ));
+                }
+            }
+            variant_views.push(views);
+        }
+        field_views.push(variant_views);
+    }
+    errors.finish()?;
+
+    let gens_with_bounds = Vec::from_iter(input.generics.params.iter().cloned());
+    let impl_gens = strip_generics_defaults(&gens_with_bounds);
+    let gens = elide_generics_bounds(&gens_with_bounds);
+    let where_clause = &input.generics.where_clause;
+    let lifetime = fresh_lifetime("'__ref__", &gens_with_bounds);
+    let vis = &input.vis;
+
+    let mut view_names = view_type_names.into_values().collect::<Vec<_>>();
+    view_names.sort();
+    let mut result = TokenStream::new();
+    for view_name in &view_names {
+        let key = view_name.to_string();
+        let mut view_variants = Vec::new();
+        let mut arms = Vec::new();
+        let mut borrows = Vec::new();
+        let mut used_types = Vec::new();
+        let mut implied_markers = Vec::new();
+        for (variant, views) in variants.iter().zip(&field_views) {
+            let variant_name = &variant.ident;
+            let cfgs = variant
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .collect::<Vec<_>>();
+            let shared = variant
+                .fields
+                .iter()
+                .enumerate()
+                .zip(views)
+                .filter(|(_, views)| views.contains(&key))
+                .map(|((index, field), _)| (field_member(index, field), field))
+                .collect::<Vec<_>>();
+            for_ch! {
+                for (index, field) in variant.fields.iter().enumerate();
+                if !shared.iter().any(|(member, _)| *member == field_member(index, field));
+                if let syn::Type::Reference(syn::TypeReference { lifetime: Some(lifetime), elem, .. }) = &field.ty;
+                implied_markers.push(quote::quote!(&#lifetime #elem));
+            }
+            used_types.extend(shared.iter().map(|(_, field)| &field.ty));
+            borrows.extend(
+                shared
+                    .iter()
+                    .map(|(member, _)| format!("`{}` of `{variant_name}`", quote::quote!(#member))),
+            );
+
+            let bindings = shared
+                .iter()
+                .map(|(member, _)| match member {
+                    syn::Member::Named(ident) => ident.clone(),
+                    syn::Member::Unnamed(index) => {
+                        Ident::new(&format!("__viu_{}", index.index), Span::call_site())
+                    }
+                })
+                .collect::<Vec<_>>();
+            let patterns =
+                shared
+                    .iter()
+                    .zip(&bindings)
+                    .map(|((member, _), binding)| match member {
+                        syn::Member::Named(_) => quote::quote!(#binding),
+                        syn::Member::Unnamed(_) => quote::quote!(#member: #binding),
+                    });
+            let docs = shared.iter().map(|(_, field)| {
+                field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("doc") || attr.path.is_ident("cfg"))
+                    .collect::<Vec<_>>()
+            });
+            let types = shared.iter().map(|(_, field)| &field.ty);
+            let (variant_def, construct) = match (&variant.fields, &shared[..]) {
+                (_, []) => (
+                    quote::quote!(#variant_name),
+                    quote::quote!(#view_name::#variant_name),
+                ),
+                (syn::Fields::Unnamed(_), _) => (
+                    quote::quote!(#variant_name(#(#(#docs)* &#lifetime #types),*)),
+                    quote::quote!(#view_name::#variant_name(#(#bindings),*)),
+                ),
+                _ => (
+                    quote::quote!(#variant_name { #(#(#docs)* #bindings: &#lifetime #types),* }),
+                    quote::quote!(#view_name::#variant_name { #(#bindings),* }),
+                ),
+            };
+            view_variants.push(quote::quote!(#(#cfgs)* #variant_def));
+            arms.push(quote::quote! {
+                #(#cfgs)*
+                #enum_name::#variant_name { #(#patterns,)* .. } => #construct
+            });
+        }
+
+        // the parameters of the enum no shared field names, and the lifetime when no field is
+        // shared at all, are kept by a variant which can't be built
+        let mentions = |name: &Ident| {
+            fn walk(tokens: TokenStream, name: &Ident) -> bool {
+                tokens.into_iter().any(|token| match token {
+                    TokenTree::Group(group) => walk(group.stream(), name),
+                    TokenTree::Ident(ident) => ident == *name,
+                    _ => false,
+                })
+            }
+            used_types.iter().any(|ty| walk(quote::quote!(#ty), name))
+        };
+        let mut markers = implied_markers;
+        if used_types.is_empty() {
+            markers.push(quote::quote!(&#lifetime ()));
+        }
+        for_ch! {
+            for param in &gens_with_bounds;
+            match param {
+                syn::GenericParam::Lifetime(def) if !mentions(&def.lifetime.ident) => {
+                    let lifetime = &def.lifetime;
+                    markers.push(quote::quote!(&#lifetime ()));
+                }
+                syn::GenericParam::Type(ty) if !mentions(&ty.ident) => {
+                    let ident = &ty.ident;
+                    markers.push(quote::quote!(fn() -> *const #ident));
+                }
+                _ => {}
+            }
+        }
+        let marker_variant = (!markers.is_empty()).then(|| {
+            quote::quote! {
+                #[doc(hidden)]
+                __ViuMarker(
+                    ::core::marker::PhantomData<(#(#markers,)*)>,
+                    ::core::convert::Infallible,
+                ),
+            }
+        });
+
+        let docs = view_docs.remove(&key).unwrap_or_default();
+        let separator = (!docs.is_empty()).then(|| quote::quote!(#[doc = ""]));
+        let summary = match &borrows[..] {
+            [] => format!("A view of [`{enum_name}`] borrowing no field."),
+            [borrows @ .., last] if !borrows.is_empty() => format!(
+                "A view of [`{enum_name}`] borrowing {} and {last} shared.",
+                borrows.join(", ")
+            ),
+            borrows => format!(
+                "A view of [`{enum_name}`] borrowing {} shared.",
+                borrows.join(", ")
+            ),
+        };
+        // a view of shared borrows only is always `Copy`, like the views of structs
+        let derives = view_derives
+            .remove(&key)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|path| !(path.is_ident("Clone") || path.is_ident("Copy")))
+            .collect::<Vec<_>>();
+        let name = ident_name(view_name);
+        let method_name = Ident::new(&format!("as_{}", to_snake_case(&name)), view_name.span());
+        let method_doc =
+            format!("Borrows the fields of view [`{view_name}`] out of the variant of `self`.");
+
+        result.extend(quote::quote! {
+            #(#[doc = #docs])*
+            #separator
+            #[doc = #summary]
+            #[derive(#(#derives),*)]
+            #[allow(non_camel_case_types, dead_code)]
+            #vis enum #view_name <#lifetime, #(#gens_with_bounds,)*>
+            #where_clause
+            {
+                #(#view_variants,)*
+                #marker_variant
+            }
+
+            impl <#lifetime, #(#impl_gens,)*> ::core::clone::Clone
+            for #view_name <#lifetime, #(#gens,)*>
+            #where_clause
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl <#lifetime, #(#impl_gens,)*> ::core::marker::Copy
+            for #view_name <#lifetime, #(#gens,)*>
+            #where_clause
+            {
+            }
+
+            impl <#lifetime, #(#impl_gens,)*> ::viu::View
+            for #view_name <#lifetime, #(#gens,)*>
+            #where_clause
+            {
+                type Source = #enum_name < #(#gens,)* >;
+
+                const NAME: &'static str = #name;
+            }
+
+            impl < #(#impl_gens,)* > #enum_name < #(#gens,)* >
+            #where_clause
+            {
+                #[doc = #method_doc]
+                #[inline]
+                #[track_caller]
+                #vis fn #method_name(&self) -> #view_name <'_, #(#gens,)*> {
+                    match self {
+                        #(#arms,)*
+                    }
+                }
+            }
+
+            impl <#lifetime, #(#impl_gens,)*> ::core::convert::From<&#lifetime #enum_name < #(#gens,)* >>
+            for #view_name <#lifetime, #(#gens,)*>
+            #where_clause
+            {
+                #[inline]
+                fn from(src: &#lifetime #enum_name < #(#gens,)* >) -> Self {
+                    src.#method_name()
+                }
+            }
+        });
+    }
+
+    Ok(result)
+}
+
+/// The fields of the struct, and whether it is in fact a union.
+fn guard_struct(ident: &Ident, ty: syn::Data) -> syn::Result<(syn::Fields, bool)> {
+    use syn::{Data::*, DataStruct, DataUnion, Fields::*};
+    match ty {
+        Struct(DataStruct {
+            fields: fields @ (Named(_) | Unnamed(_)),
+            ..
+        }) => Ok((fields, false)),
+        Union(DataUnion { fields, .. }) => Ok((Named(fields), true)),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            "`view_as` can only apply on named or tuple struct, union or enum",
+        )),
+    }
+}
+
+/// Removes the defaults of the generic parameters, which only the struct
+/// definitions may have, e.g. `<T = u8, const N: usize = 4>` into `<T, const N: usize>`.
+fn strip_generics_defaults(gens: &[syn::GenericParam]) -> Vec<syn::GenericParam> {
+    use syn::GenericParam::*;
+    gens.iter()
+        .cloned()
+        .map(|param| match param {
+            Type(ty) => Type(syn::TypeParam {
+                eq_token: None,
+                default: None,
+                ..ty
+            }),
+            Const(c) => Const(syn::ConstParam {
+                eq_token: None,
+                default: None,
+                ..c
+            }),
+            lifetime => lifetime,
+        })
+        .collect()
+}
+
+/// Turns the generic parameters into the arguments referring to them,
+/// e.g. `<'a: 'b, T: Clone, const N: usize>` into `<'a, T, N>`.
+/// Only used at argument positions: the generated items still declare the
+/// parameters with their bounds, so that the forwarded `where` clause can
+/// name associated types like `T::Item`.
+fn elide_generics_bounds(gens: &[syn::GenericParam]) -> Vec<syn::GenericArgument> {
+    use syn::GenericArgument;
+    use syn::GenericParam::*;
+    gens.iter()
+        .map(|param| match param {
+            Type(ty) => {
+                let ident = &ty.ident;
+                GenericArgument::Type(syn::parse_quote!(#ident))
+            }
+            Lifetime(lifetime) => GenericArgument::Lifetime(lifetime.lifetime.clone()),
+            Const(c) => {
+                let ident = &c.ident;
+                GenericArgument::Const(syn::parse_quote!(#ident))
+            }
+        })
+        .collect()
+}
+
+/// The names of the generated views, by their names in `view_as`: those are given the prefix
+/// and suffix of `view_naming`, while the other attributes keep naming the views as declared.
+fn view_idents_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Ident>> {
+    let mut naming = None;
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_NAMING, Span::call_site()));
+        if naming.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("`{VIEW_NAMING}` is set more than once"),
+            ));
+        }
+        naming = Some(parse_parenthesized::<ViewNaming>(attr, "suffix = \"View\"")?);
+    }
+    let Some(ViewNaming { prefix, suffix }) = naming else {
+        return Ok(view_names.clone());
+    };
+
+    let affix =
+        |lit: &Option<syn::LitStr>| lit.as_ref().map(syn::LitStr::value).unwrap_or_default();
+    let span = prefix.as_ref().or(suffix.as_ref()).map(syn::LitStr::span);
+    view_names
+        .iter()
+        .map(|(name, view)| {
+            let renamed = format!("{}{}{}", affix(&prefix), ident_name(view), affix(&suffix));
+            let mut ident = syn::parse_str::<Ident>(&renamed).map_err(|_| {
+                syn::Error::new(
+                    span.unwrap_or_else(|| view.span()),
+                    format!("view `{view}` would be named `{renamed}`, which is not an identifier"),
+                )
+            })?;
+            ident.set_span(view.span());
+            Ok((name.clone(), ident))
+        })
+        .collect()
+}
+
+fn view_type_names_from_attrs(attrs: &[syn::Attribute]) -> syn::Result<HashMap<String, Ident>> {
+    let mut names = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_AS, Span::call_site()));
+        // a bare `#[view_as]` declares no views, like `#[view_as()]`, for generated code
+        if !attr.tokens.is_empty();
+        let decls = parse_parenthesized::<ViewDecls>(attr, "Name1, Name2(ref: a; mut: b), ...")?;
+        for ViewDecl { view, .. } in decls.elems;
+        insert_view_name(&mut names, view)?;
+    }
+
+    Ok(names)
+}
+
+/// The fields declared along with their view by `#[view_as(Foo(ref: a; mut: b))]`.
+fn declared_view_fields_from_attrs(
+    attrs: &[syn::Attribute],
+    original_ty_fields: &syn::Fields,
+) -> syn::Result<HashMap<String, Vec<(Sharable, syn::Member)>>> {
+    let mut declared_fields = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_AS, Span::call_site()));
+        if !attr.tokens.is_empty();
+        let decls = syn::parse2::<ViewDecls>(attr.tokens.to_owned())?;
+        for ViewDecl { view, fields } in decls.elems;
+        if let Some(fields) = fields;
+        for_ch! {
+            for (i, (_, member)) in fields.iter().enumerate();
+            let name = quote::quote!(#member).to_string();
+            if !original_ty_fields
+                .iter()
+                .enumerate()
+                .any(|(index, field)| &field_member(index, field) == member)
+            {
+                return Err(syn::Error::new_spanned(
+                    member,
+                    format!("no field `{name}` to put in view `{view}`"),
+                ));
+            }
+            if fields[..i].iter().any(|(_, prev)| prev == member) {
+                return Err(syn::Error::new_spanned(
+                    member,
+                    format!("field `{name}` is listed more than once in view `{view}`"),
+                ));
+            }
+        };
+        declared_fields.insert(view.to_string(), fields);
+    }
+
+    Ok(declared_fields)
+}
+
+/// Declares a view, rejecting the names already declared, even if they only differ by case,
+/// since their `as_` methods would collide.
+fn insert_view_name(names: &mut HashMap<String, Ident>, ident: Ident) -> syn::Result<()> {
+    let snake_name = to_snake_case(&ident_name(&ident));
+    if names.contains_key(&ident.to_string()) {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("view `{ident}` is declared more than once"),
+        ));
+    }
+    if let Some(other) = names
+        .values()
+        .find(|other| to_snake_case(&ident_name(other)) == snake_name)
+    {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("views `{other}` and `{ident}` would both generate `as_{snake_name}`"),
+        ));
+    }
+
+    names.insert(ident.to_string(), ident);
+    Ok(())
+}
+
+/// Collects the views declared by `#[view_all_ref(...)]` and `#[view_all_mut(...)]`,
+/// which borrow every field with the given sharability.
+fn whole_views_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &mut HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Sharable>> {
+    let mut whole_views = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        let share = if attr.path.is_ident(&Ident::new(VIEW_ALL_REF, Span::call_site())) {
+            Ref
+        } else if attr.path.is_ident(&Ident::new(VIEW_ALL_MUT, Span::call_site())) {
+            Mut
+        } else {
+            continue;
+        };
+        let idents = parse_parenthesized::<IdentTuple>(attr, "Name1, Name2, ...")?;
+        for ident in idents.elems;
+        whole_views.insert(ident.to_string(), share);
+        insert_view_name(view_names, ident)?;
+    }
+
+    Ok(whole_views)
+}
+
+/// Collects the views declared by `#[view_rest_mut(...)]`, with the fields they leave out.
+fn rest_views_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &mut HashMap<String, Ident>,
+    original_ty_fields: &syn::Fields,
+) -> syn::Result<HashMap<String, Vec<syn::Member>>> {
+    let mut rest_views = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_REST_MUT, Span::call_site()));
+        let ViewRest { view, except } =
+            parse_parenthesized::<ViewRest>(attr, "Main, except(a, b)")?;
+        let except = Vec::from_iter(except);
+        if let Some(member) = except.iter().find(|member| {
+            !original_ty_fields
+                .iter()
+                .enumerate()
+                .any(|(index, field)| &field_member(index, field) == *member)
+        }) {
+            return Err(syn::Error::new_spanned(
+                member,
+                format!(
+                    "no field `{}` to leave out of view `{view}`",
+                    quote::quote!(#member)
+                ),
+            ));
+        }
+        rest_views.insert(view.to_string(), except);
+        insert_view_name(view_names, view)?;
+    }
+
+    Ok(rest_views)
+}
+
+/// The members and types of the fields of a split group.
+type SplitGroupFields = Vec<(syn::Member, syn::Type)>;
+
+/// The groups of `#[split_group(G: a, b)]`, or of `#[ref_split(G: a, b)]` for `attr_name`.
+fn split_groups_from_attrs(
+    attrs: &[syn::Attribute],
+    attr_name: &str,
+    original_ty_fields: &syn::Fields,
+) -> syn::Result<Vec<(Ident, SplitGroupFields)>> {
+    let mut groups = Vec::<(Ident, SplitGroupFields)>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(attr_name, Span::call_site()));
+        let SplitGroup { group, members } = parse_parenthesized::<SplitGroup>(attr, "G: a, b")?;
+        if groups.iter().any(|(prev, _)| prev == &group) {
+            return Err(syn::Error::new(
+                group.span(),
+                format!("split group `{group}` is declared more than once"),
+            ));
+        }
+        let mut group_fields = SplitGroupFields::new();
+        for member in members {
+            let name = quote::quote!(#member).to_string();
+            let field = original_ty_fields
+                .iter()
+                .enumerate()
+                .find(|(index, field)| field_member(*index, field) == member)
+                .map(|(_, field)| field);
+            let field = match field {
+                Some(field) => field,
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        member,
+                        format!("no field `{name}` to put in split group `{group}`"),
+                    ))
+                }
+            };
+            if group_fields.iter().any(|(prev, _)| prev == &member) {
+                return Err(syn::Error::new_spanned(
+                    member,
+                    format!("field `{name}` is in split group `{group}` more than once"),
+                ));
+            }
+            // a tuple has no place for a field that may not exist
+            if field.attrs.iter().any(|attr| attr.path.is_ident("cfg")) {
+                return Err(syn::Error::new_spanned(
+                    member,
+                    format!("field `{name}` under `#[cfg]` can't be in split group `{group}`"),
+                ));
+            }
+            group_fields.push((member, field.ty.clone()));
+        }
+        groups.push((group, group_fields));
+    }
+
+    Ok(groups)
+}
+
+fn check_view_references(
+    view_names: &HashMap<String, Ident>,
+    whole_views: &HashMap<String, Sharable>,
+    original_ty_fields: &syn::Fields,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+
+    for_ch! {
+        for field in original_ty_fields;
+        for attr in &field.attrs;
+        for (_, FieldView { view: view_ident, .. }) in errors.take(field_views_of_attr(attr));
+        if whole_views.contains_key(&view_ident.to_string()) {
+            errors.push(syn::Error::new(
+                view_ident.span(),
+                format!("view `{view_ident}` already borrows every field"),
+            ));
+        }
+        if !view_names.contains_key(&view_ident.to_string()) {
+            errors.push(undeclared_view_error(&view_ident, view_names));
+        }
+    }
+
+    for_ch! {
+        for field in original_ty_fields;
+        for attr in &field.attrs;
+        if attr.path.is_ident(&Ident::new(PROJECT_IN, Span::call_site()));
+        if let Some(projections) = errors.ok(parse_parenthesized::<ProjectIn>(attr, "V -> InnerSub"));
+        for Projection { view, .. } in projections.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            errors.push(undeclared_view_error(&view, view_names));
+        }
+    }
+
+    for_ch! {
+        for field in original_ty_fields;
+        for attr in &field.attrs;
+        if attr.path.is_ident(&Ident::new(REBORROW_IN, Span::call_site()));
+        if let Some(view_idents) = errors.ok(parse_parenthesized::<IdentTuple>(attr, "View1, View2, ..."));
+        for view_ident in view_idents.elems;
+        if !view_names.contains_key(&view_ident.to_string()) {
+            errors.push(undeclared_view_error(&view_ident, view_names));
+        }
+    }
+
+    errors.finish()
+}
+
+fn undeclared_view_error(view_ident: &Ident, view_names: &HashMap<String, Ident>) -> syn::Error {
+    let mut msg = format!("view `{view_ident}` is not declared in `{VIEW_AS}`");
+    if let Some(closest) = closest_name(&view_ident.to_string(), view_names) {
+        msg += &format!(", did you mean `{closest}`?");
+    }
+    syn::Error::new(view_ident.span(), msg)
+}
+
+/// Collects the views named by attributes like `#[view_debug(Foo, Bar)]`,
+/// where a bare `#[view_debug]` selects all the views.
+fn views_selected_by_attrs(
+    attrs: &[syn::Attribute],
+    attr_name: &str,
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashSet<String>> {
+    let mut selected = HashSet::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(attr_name, Span::call_site()));
+        let idents = parse_parenthesized_or_bare::<IdentTuple>(attr, "View1, View2, ...")?;
+        if idents.elems.is_empty() {
+            selected.extend(view_names.keys().cloned());
+        }
+        for ident in idents.elems;
+        if !view_names.contains_key(&ident.to_string()) {
+            return Err(undeclared_view_error(&ident, view_names));
+        }
+        selected.insert(ident.to_string());
+    }
+
+    Ok(selected)
+}
+
+fn view_subsets_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<Vec<ViewSubset>> {
+    let mut subsets = Vec::new();
+
+    for_ch! {
+        for attr in attrs;
+        let subset = if attr.path.is_ident(&Ident::new(VIEW_SUBSET, Span::call_site())) {
+            parse_parenthesized::<ViewSubset>(attr, "Small: from Big")?
+        } else if attr.path.is_ident(&Ident::new(VIEW_NARROW, Span::call_site())) {
+            parse_parenthesized::<ViewNarrow>(attr, "Big => Small")?.0
+        } else {
+            continue;
+        };
+        if let Some(view) = [&subset.small, &subset.big]
+            .into_iter()
+            .find(|view| !view_names.contains_key(&view.to_string()))
+        {
+            return Err(undeclared_view_error(view, view_names));
+        }
+        subsets.push(subset);
+    }
+
+    Ok(subsets)
+}
+
+fn view_splits_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<Vec<(Ident, Ident)>> {
+    let mut splits = Vec::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_SPLIT, Span::call_site()));
+        let idents = parse_parenthesized_or_bare::<IdentTuple>(attr, "Left, Right")?;
+        if idents.elems.len() != 2 {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("`{VIEW_SPLIT}` expects exactly two views"),
+            ));
+        }
+        if let Some(view) = idents
+            .elems
+            .iter()
+            .find(|view| !view_names.contains_key(&view.to_string()))
+        {
+            return Err(undeclared_view_error(view, view_names));
+        }
+        let mut views = idents.elems.into_iter();
+        splits.push((views.next().unwrap(), views.next().unwrap()));
+    }
+
+    Ok(splits)
+}
+
+fn disjoint_views_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<Vec<Vec<Ident>>> {
+    let mut groups = Vec::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEWS_DISJOINT, Span::call_site()));
+        let idents = parse_parenthesized_or_bare::<IdentTuple>(attr, "A, B, C")?;
+        if let Some(view) = idents
+            .elems
+            .iter()
+            .find(|view| !view_names.contains_key(&view.to_string()))
+        {
+            return Err(undeclared_view_error(view, view_names));
+        }
+        groups.push(Vec::from_iter(idents.elems));
+    }
+
+    Ok(groups)
+}
+
+/// The traits listed by `#[view_derive(Foo: Debug)]` or `#[view_assert(Foo: Send)]`,
+/// for every view.
+fn view_traits_from_attrs(
+    attrs: &[syn::Attribute],
+    attr_name: &str,
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<syn::Path>>> {
+    let mut derives = HashMap::<_, Vec<_>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(attr_name, Span::call_site()));
+        let view_derive = parse_parenthesized::<ViewDerive>(attr, "Foo: Trait1, Trait2")?;
+        let targets = match &view_derive.view {
+            Some(view) if !view_names.contains_key(&view.to_string()) => {
+                return Err(undeclared_view_error(view, view_names));
+            }
+            Some(view) => vec![view.to_string()],
+            None => view_names.keys().cloned().collect(),
+        };
+        for target in targets;
+        derives
+            .entry(target)
+            .or_default()
+            .extend(view_derive.derives.iter().cloned());
+    }
+
+    Ok(derives)
+}
+
+/// The attributes forwarded onto the fields of each view: all but viu's own for a bare
+/// `#[view_forward_attrs]`, or those listed by `#[view_forward_attrs(Foo: serde)]`.
+fn forwarded_attrs_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Option<Vec<syn::Path>>>> {
+    let mut forwarded = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_FORWARD_ATTRS, Span::call_site()));
+        if attr.tokens.is_empty() {
+            for view in view_names.keys() {
+                forwarded.insert(view.clone(), None);
+            }
+            continue;
+        }
+        let listed = parse_parenthesized::<ViewDerive>(attr, "Foo: serde, ...")?;
+        let targets = match &listed.view {
+            Some(view) if !view_names.contains_key(&view.to_string()) => {
+                return Err(undeclared_view_error(view, view_names));
+            }
+            Some(view) => vec![view.to_string()],
+            None => view_names.keys().cloned().collect(),
+        };
+        for target in targets;
+        // forwarding everything already covers any list
+        if let Some(allowed) = forwarded.entry(target).or_insert_with(|| Some(Vec::new()));
+        allowed.extend(listed.derives.iter().cloned());
+    }
+
+    Ok(forwarded)
+}
+
+fn view_vises_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, syn::Visibility>> {
+    let mut vises = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_VIS, Span::call_site()));
+        let view_vis = parse_parenthesized::<ViewVis>(attr, "Foo = pub(crate)")?;
+        for (view, vis) in view_vis.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        if vises.insert(view.to_string(), vis).is_some() {
+            return Err(syn::Error::new(
+                view.span(),
+                format!("the visibility of view `{view}` is set more than once"),
+            ));
+        }
+    }
+
+    Ok(vises)
+}
+
+fn view_markers_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<syn::Type>>> {
+    let mut markers = HashMap::<_, Vec<_>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_MARKER, Span::call_site()));
+        let view_marker = parse_parenthesized::<ViewMarker>(attr, "Foo = Marker")?;
+        for (view, marker) in view_marker.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        markers.entry(view.to_string()).or_default().push(marker);
+    }
+
+    Ok(markers)
+}
+
+fn view_reborrows_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Option<Ident>>> {
+    let mut reborrows = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_REBORROW, Span::call_site()));
+        let view_reborrow = parse_parenthesized::<ViewReborrow>(attr, "Foo = reborrow_foo")?;
+        for (view, method) in view_reborrow.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        if reborrows.insert(view.to_string(), method).is_some() {
+            return Err(syn::Error::new(
+                view.span(),
+                format!("the reborrow method of view `{view}` is set more than once"),
+            ));
+        }
+    }
+
+    Ok(reborrows)
+}
+
+fn view_delegates_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<syn::Signature>>> {
+    let mut delegates = HashMap::<_, Vec<_>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_DELEGATE, Span::call_site()));
+        let ViewDelegate { view, sigs } =
+            parse_parenthesized::<ViewDelegate>(attr, "Foo: fn method(&mut self, arg: T) -> R")?;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        delegates.entry(view.to_string()).or_default().extend(sigs);
+    }
+
+    Ok(delegates)
+}
+
+#[allow(clippy::type_complexity)]
+fn view_aliases_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<(Option<syn::AngleBracketedGenericArguments>, Ident)>>> {
+    let mut aliases = HashMap::<_, Vec<(Option<_>, _)>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_ALIAS, Span::call_site()));
+        let view_alias = parse_parenthesized::<ViewAlias>(attr, "Foo = FooIn")?;
+        for (view, args, alias) in view_alias.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        let view_aliases = aliases.entry(view.to_string()).or_default();
+        // a view can have any number of aliases binding its type parameters, but a single generic one
+        if args.is_none() && view_aliases.iter().any(|(args, _)| args.is_none()) {
+            return Err(syn::Error::new(
+                view.span(),
+                format!("the alias of view `{view}` is set more than once"),
+            ));
+        }
+        view_aliases.push((args, alias));
+    }
+
+    Ok(aliases)
+}
+
+fn view_wraps_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, syn::Path>> {
+    let mut wraps = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_WRAP, Span::call_site()));
+        let view_wrap = parse_parenthesized::<ViewWrap>(attr, "Foo = Wrapper")?;
+        for (view, wrap) in view_wrap.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        if wraps.insert(view.to_string(), wrap).is_some() {
+            return Err(syn::Error::new(
+                view.span(),
+                format!("the wrapper of view `{view}` is set more than once"),
+            ));
+        }
+    }
+
+    Ok(wraps)
+}
+
+fn view_reprs_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<syn::Meta>>> {
+    let mut reprs = HashMap::<_, Vec<_>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_REPR, Span::call_site()));
+        let view_repr = parse_parenthesized::<ViewRepr>(attr, "Foo = C")?;
+        for (view, repr) in view_repr.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        let path = repr.path();
+        // `packed` would forbid borrowing the fields of the view, which is all it's made of
+        let supported = match &repr {
+            syn::Meta::Path(_) => path.is_ident("C") || path.is_ident("transparent"),
+            syn::Meta::List(_) => path.is_ident("align"),
+            syn::Meta::NameValue(_) => false,
+        };
+        if !supported {
+            return Err(syn::Error::new_spanned(
+                &repr,
+                format!(
+                    "view `{view}` can't be `repr({})`, expected `C`, `transparent` or `align(N)`",
+                    quote::quote!(#repr)
+                ),
+            ));
+        }
+        reprs.entry(view.to_string()).or_default().push(repr);
+    }
+
+    Ok(reprs)
+}
+
+fn view_pairs_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, ViewPair>> {
+    let mut pairs = HashMap::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_PAIR, Span::call_site()));
+        let view_pairs = parse_parenthesized::<ViewPairs>(attr, "Foo, Bar")?;
+        for pair in view_pairs.elems;
+        if !view_names.contains_key(&pair.view.to_string()) {
+            return Err(undeclared_view_error(&pair.view, view_names));
+        }
+        let view = pair.view.clone();
+        if pairs.insert(view.to_string(), pair).is_some() {
+            return Err(syn::Error::new(
+                view.span(),
+                format!("view `{view}` is paired more than once"),
+            ));
+        }
+    }
+
+    Ok(pairs)
+}
+
+fn view_docs_from_attrs(
+    attrs: &[syn::Attribute],
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<HashMap<String, Vec<syn::LitStr>>> {
+    let mut docs = HashMap::<_, Vec<_>>::new();
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_DOC, Span::call_site()));
+        let view_doc = parse_parenthesized::<ViewDoc>(attr, "Foo = \"...\"")?;
+        for (view, doc) in view_doc.elems;
+        if !view_names.contains_key(&view.to_string()) {
+            return Err(undeclared_view_error(&view, view_names));
+        }
+        docs.entry(view.to_string()).or_default().push(doc);
+    }
+
+    Ok(docs)
+}
+
+/// Whether `#[view_macro(local)]` asks for the ctor macros not to be exported,
+/// `#[view_macro(export)]` is the default.
+fn local_macros_from_attrs(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut local = false;
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEW_MACRO, Span::call_site()));
+        let idents = syn::parse2::<IdentTuple>(attr.tokens.to_owned())?;
+        let mut idents = idents.elems.into_iter();
+        match (idents.next(), idents.next()) {
+            (Some(ident), None) if ident == "local" => local = true,
+            (Some(ident), None) if ident == "export" => local = false,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("expected `#[{VIEW_MACRO}(local)]` or `#[{VIEW_MACRO}(export)]`"),
+                ))
+            }
+        }
+    }
+
+    Ok(local)
+}
+
+/// `#[views_module(name)]` or `#[views_module(crate::path::to::name)]`, the module to generate
+/// the items in, along with the path the ctor macros name it by: the name, resolved where they
+/// are called, or the path out of `$crate`.
+fn views_module_from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Option<(Ident, TokenStream)>> {
+    let mut module = None;
+
+    for_ch! {
+        for attr in attrs;
+        if attr.path.is_ident(&Ident::new(VIEWS_MODULE, Span::call_site()));
+        let expected = || {
+            syn::Error::new_spanned(
+                attr,
+                format!("expected a single `#[{VIEWS_MODULE}(name)]` or `#[{VIEWS_MODULE}(crate::path::to::name)]`"),
+            )
+        };
+        let path = parse_parenthesized::<ModulePath>(attr, "name")?.0;
+        let segments = path.segments.iter().map(|segment| &segment.ident).collect::<Vec<_>>();
+        match segments[..] {
+            _ if module.is_some() || path.leading_colon.is_some() => return Err(expected()),
+            [name] if name != "crate" => module = Some((name.clone(), quote::quote!(#name))),
+            [root, ref rest @ .., name] if root == "crate" => {
+                module = Some((name.clone(), quote::quote!($crate #(:: #rest)* :: #name)))
+            }
+            _ => return Err(expected()),
+        }
+    }
+
+    Ok(module)
+}
+
+/// The visibility in a child module that is equivalent to `vis` in its parent.
+fn nested_vis(vis: &syn::Visibility) -> syn::Visibility {
+    match vis {
+        syn::Visibility::Inherited => syn::parse_quote!(pub(super)),
+        syn::Visibility::Restricted(restricted) => {
+            let path = &restricted.path;
+            match path.segments.first() {
+                Some(first) if first.ident == "crate" => vis.clone(),
+                Some(first) if first.ident == "self" => {
+                    let rest = path.segments.iter().skip(1);
+                    syn::parse_quote!(pub(in super #(::#rest)*))
+                }
+                _ => syn::parse_quote!(pub(in super::#path)),
+            }
+        }
+        syn::Visibility::Public(_) | syn::Visibility::Crate(_) => vis.clone(),
+    }
+}
+
+fn closest_name<'a>(name: &str, candidates: &'a HashMap<String, Ident>) -> Option<&'a str> {
+    candidates
+        .keys()
+        .min_by_key(|candidate| (edit_distance(name, candidate), candidate.as_str()))
+        .map(String::as_str)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diag + (ca != *cb) as usize).min(row[j] + 1).min(above + 1);
+            diag = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Selects the fields of a view, `whole` is the sharability of every field
+/// of a view declared by `view_all_ref` or `view_all_mut`, `rest` the
+/// fields left out of a view declared by `view_rest_mut`, and `declared` the
+/// fields listed along with the view in `view_as`. `through` reborrows every
+/// reference field that can be, as by `view_borrow_through`.
+fn view_type_fields(
+    view_name: &Ident,
+    whole: Option<Sharable>,
+    rest: Option<&Vec<syn::Member>>,
+    declared: Option<&Vec<(Sharable, syn::Member)>>,
+    through: bool,
+    original_ty_fields: &syn::Fields,
+) -> syn::Result<Vec<ViewField>> {
+    let mut res = Vec::new();
+
+    for (index, field) in original_ty_fields.iter().enumerate() {
+        let member = field_member(index, field);
+        let mut share = declared
+            .into_iter()
+            .flatten()
+            .find(|(_, declared)| declared == &member)
+            .map(|(share, _)| *share)
+            .or(whole);
+        let mut projections = Vec::new();
+        let mut reborrow = None;
+        let mut rename = None::<Ident>;
+        let mut retype = None::<syn::TypeReference>;
+        let mut transpose = false;
+        let mut borrow = false;
+
+        for_ch! {
+            for attr in &field.attrs;
+            for_ch! {
+                if attr.path.is_ident(&Ident::new(REBORROW_IN, Span::call_site()));
+                let view_idents = parse_parenthesized::<IdentTuple>(attr, "View1, View2, ...")?;
+                for view_ident in view_idents.elems;
+                if &view_ident == view_name;
+                reborrow = Some(view_ident);
+            };
+
+            for_ch! {
+                for (next, FieldView { view, rename: next_rename, retype: next_retype, transpose: next_transpose }) in field_views_of_attr(attr)?;
+                if &view == view_name;
+                share = Some(merge_sharable(share, next, &member, attr, view_name)?);
+                for_ch! {
+                    if let Some(next_retype) = next_retype;
+                    let expected = match next {
+                        Ref if next_retype.mutability.is_none() => None,
+                        Mut if next_retype.mutability.is_some() => None,
+                        Ref => Some("must be a shared reference `&T`"),
+                        Mut => Some("must be a mutable reference `&mut T`"),
+                        Copied | Cloned => Some("can't be another one, it's held by value"),
+                    };
+                    if let Some(expected) = expected {
+                        return Err(syn::Error::new_spanned(
+                            &next_retype,
+                            format!(
+                                "field `{}` is put in view `{view_name}` by `{}`, its type there {expected}",
+                                quote::quote!(#member),
+                                next.attr_name(),
+                            ),
+                        ));
+                    }
+                    if let Some(prev_retype) = &retype {
+                        if quote::quote!(#prev_retype).to_string() != quote::quote!(#next_retype).to_string() {
+                            return Err(syn::Error::new_spanned(
+                                &next_retype,
+                                format!(
+                                    "field `{}` is given two types in view `{view_name}`",
+                                    quote::quote!(#member)
+                                ),
+                            ));
+                        }
+                    }
+                    retype = Some(next_retype);
+                };
+                transpose |= next_transpose;
+                borrow |= attr.path.is_ident(BORROW_IN);
+                for_ch! {
+                    if let Some(next_rename) = next_rename;
+                    if let Some(prev_rename) = &rename {
+                        if prev_rename != &next_rename {
+                            return Err(syn::Error::new(
+                                next_rename.span(),
+                                format!(
+                                    "field `{}` is renamed both `{prev_rename}` and `{next_rename}` in view `{view_name}`",
+                                    quote::quote!(#member)
+                                ),
+                            ));
+                        }
+                    }
+                    rename = Some(next_rename);
+                };
+            };
+
+            for_ch! {
+                if attr.path.is_ident(&Ident::new(PROJECT_IN, Span::call_site()));
+                let attr_projections = parse_parenthesized::<ProjectIn>(attr, "V -> InnerSub")?;
+                for Projection { view, target } in attr_projections.elems;
+                if &view == view_name;
+                projections.push((view, target));
+            };
+        }
+
+        // the fields not put in the view explicitly are borrowed mutably by it
+        if let (None, Some(except)) = (share, rest) {
+            if !except.contains(&member) {
+                share = Some(Mut);
+            }
+        }
+
+        if let (None, Some((view, _))) = (share, projections.first()) {
+            return Err(syn::Error::new(
+                view.span(),
+                format!(
+                    "field `{}` is projected in view `{view_name}` but isn't borrowed by it",
+                    quote::quote!(#member)
+                ),
+            ));
+        }
+
+        let ty = match (share, reborrow) {
+            (None, Some(view)) => {
+                return Err(syn::Error::new(
+                    view.span(),
+                    format!(
+                        "field `{}` is reborrowed in view `{view_name}` but isn't borrowed by it",
+                        quote::quote!(#member)
+                    ),
+                ))
+            }
+            (Some(share), Some(view)) => match &field.ty {
+                syn::Type::Reference(reference)
+                    if share != Mut || reference.mutability.is_some() =>
+                {
+                    Some((*reference.elem).clone())
+                }
+                syn::Type::Reference(_) => {
+                    return Err(syn::Error::new(
+                        view.span(),
+                        format!(
+                            "field `{}` is a shared reference, it can't be reborrowed mutably",
+                            quote::quote!(#member)
+                        ),
+                    ))
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        view.span(),
+                        format!(
+                            "field `{}` must be a reference to be reborrowed",
+                            quote::quote!(#member)
+                        ),
+                    ))
+                }
+            },
+            (Some(Ref | Mut), None) if through => match &field.ty {
+                syn::Type::Reference(reference)
+                    if share != Some(Mut) || reference.mutability.is_some() =>
+                {
+                    Some((*reference.elem).clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // a field given another type is coerced to it where the view is built
+        let deref = ty.is_some();
+        let ty = match retype {
+            Some(retype) => *retype.elem,
+            None => ty.unwrap_or_else(|| field.ty.clone()),
+        };
+        let cell = match (borrow, type_argument(&ty, "RefCell")) {
+            (false, _) => None,
+            (true, Some(content)) => Some(content.clone()),
+            (true, None) => {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "field `{}` must be a `RefCell` to be borrowed in view `{view_name}`",
+                        quote::quote!(#member)
+                    ),
+                ))
+            }
+        };
+        let transposed = match (transpose, type_argument(&ty, "Option")) {
+            (false, _) => None,
+            (true, Some(content)) => Some(content.clone()),
+            (true, None) => {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "field `{}` must be an `Option` to be transposed in view `{view_name}`",
+                        quote::quote!(#member)
+                    ),
+                ))
+            }
+        };
+
+        if let Some(share) = share {
+            let ident = match (rename, &member) {
+                (Some(rename), _) => rename,
+                (None, syn::Member::Named(ident)) => ident.clone(),
+                (None, syn::Member::Unnamed(index)) => {
+                    Ident::new(&format!("_{}", index.index), index.span)
+                }
+            };
+            if let Some(ViewField {
+                member: prev_member,
+                ..
+            }) = res.iter().find(|prev: &&ViewField| prev.ident == ident)
+            {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "fields `{}` and `{}` are both named `{ident}` in view `{view_name}`",
+                        quote::quote!(#prev_member),
+                        quote::quote!(#member)
+                    ),
+                ));
+            }
+            res.push(ViewField {
+                member,
+                ident,
+                vis: field.vis.clone(),
+                accessor_vis: None,
+                docs: field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("doc"))
+                    .cloned()
+                    .collect(),
+                cfgs: field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("cfg"))
+                    .cloned()
+                    .collect(),
+                forwarded: field
+                    .attrs
+                    .iter()
+                    .filter(|attr| {
+                        !["doc", "cfg"]
+                            .into_iter()
+                            .chain(FIELD_ATTRS)
+                            .any(|name| attr.path.is_ident(name))
+                    })
+                    .cloned()
+                    .collect(),
+                share,
+                deref,
+                ty,
+                projections: projections.into_iter().map(|(_, target)| target).collect(),
+                transposed,
+                wrap: None,
+                cell,
+            });
+        }
+    }
+
+    Ok(res)
+}
+
+/// The views a field attribute puts the field in, with how they borrow it:
+/// `ref_in(A, B as b)`, `mut_in(C)`, `copy_in(D)`, `borrow_in(E)` or `views(ref: A, B; mut: C)`.
+fn field_views_of_attr(attr: &syn::Attribute) -> syn::Result<Vec<(Sharable, FieldView)>> {
+    let with_share = |share: Sharable, views: FieldViewTuple| match views.transpose {
+        Some(transpose) if share != Ref => Err(syn::Error::new(
+            transpose.span(),
+            format!("`transpose` only applies to `{REF_IN}`"),
+        )),
+        _ => Ok(views.elems.into_iter().map(|view| (share, view)).collect()),
+    };
+
+    let usage = "View1, View2, ...";
+    if attr.path.is_ident(&Ident::new(REF_IN, Span::call_site()))
+        || attr
+            .path
+            .is_ident(&Ident::new(BORROW_IN, Span::call_site()))
+    {
+        with_share(Ref, parse_parenthesized(attr, usage)?)
+    } else if attr.path.is_ident(&Ident::new(MUT_IN, Span::call_site())) {
+        with_share(Mut, parse_parenthesized(attr, usage)?)
+    } else if attr.path.is_ident(&Ident::new(COPY_IN, Span::call_site())) {
+        with_share(Copied, parse_parenthesized(attr, usage)?)
+    } else if attr.path.is_ident(&Ident::new(CLONE_IN, Span::call_site())) {
+        with_share(Cloned, parse_parenthesized(attr, usage)?)
+    } else if attr.path.is_ident(&Ident::new(VIEWS, Span::call_site())) {
+        Ok(parse_parenthesized::<FieldViews>(attr, "ref: View1, View2; mut: View3")?.elems)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Spells out the sole view of the struct in the bare `#[ref_in]`, `#[mut_in]`, `#[copy_in]` and
+/// `#[clone_in]` and `#[borrow_in]` of the fields, which are ambiguous with several views.
+fn expand_bare_field_attrs(
+    fields: &mut syn::Fields,
+    view_names: &HashMap<String, Ident>,
+) -> syn::Result<()> {
+    for_ch! {
+        for field in fields.iter_mut();
+        for attr in &mut field.attrs;
+        if attr.tokens.is_empty();
+        if let Some(attr_name) = [REF_IN, MUT_IN, COPY_IN, CLONE_IN, BORROW_IN]
+            .into_iter()
+            .find(|name| attr.path.is_ident(name));
+        match view_names.values().collect::<Vec<_>>()[..] {
+            [view] => attr.tokens = quote::quote!((#view)),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &*attr,
+                    format!(
+                        "a bare `#[{attr_name}]` needs the struct to have a single view, it has {}, name the views like `#[{attr_name}(View)]`",
+                        view_names.len()
+                    ),
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Like `parse_parenthesized`, but also accepts the bare attribute, which
+/// selects every view, as in `#[view_debug]`.
+fn parse_parenthesized_or_bare<T: Parse>(attr: &syn::Attribute, usage: &str) -> syn::Result<T> {
+    if attr.tokens.is_empty() {
+        syn::parse2(attr.tokens.to_owned())
+    } else {
+        parse_parenthesized(attr, usage)
+    }
+}
+
+/// Parses the arguments of an attribute which must be parenthesized, reporting
+/// any other form like `#[view_as = "Foo"]` with the expected one, `usage`.
+fn parse_parenthesized<T: Parse>(attr: &syn::Attribute, usage: &str) -> syn::Result<T> {
+    let mut tokens = attr.tokens.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Parenthesis => {
+            syn::parse2(attr.tokens.to_owned())
+        }
+        _ => {
+            let path = &attr.path;
+            Err(syn::Error::new_spanned(
+                attr,
+                format!("expected `#[{}({usage})]`", quote::quote!(#path)),
+            ))
+        }
+    }
+}
+
+/// Warns about the fields put by `mut_in` in a view which could share them, their type being
+/// mutable through a shared reference, like `RefCell` or `Mutex`.
+fn needless_mut_field_views(fields: &syn::Fields) -> TokenStream {
+    let mut warnings = TokenStream::new();
+
+    for_ch! {
+        for (index, field) in fields.iter().enumerate();
+        let syn::Type::Path(syn::TypePath { path, .. }) = &field.ty else {
+            continue;
+        };
+        let Some(cell) = path.segments.last().map(|segment| segment.ident.to_string()) else {
+            continue;
+        };
+        if ["Cell", "RefCell", "Mutex", "RwLock"].contains(&&*cell);
+        for attr in &field.attrs;
+        for (share, FieldView { view, .. }) in field_views_of_attr(attr).unwrap_or_default();
+        if share == Mut;
+        let member = field_member(index, field);
+        warnings.extend(construct_warning(
+            view.span(),
+            "needless_mut_in",
+            &format!(
+                "field `{}` is a `{cell}`, which can be mutated through `{REF_IN}({view})` already",
+                quote::quote!(#member)
+            ),
+        ));
+    }
+
+    warnings
+}
+
+/// Warns about the fields put in a view twice the same way, like by `#[ref_in(V)] #[ref_in(V)]`,
+/// which is harmless but likely a copy-paste mistake.
+fn redundant_field_views(fields: &syn::Fields) -> TokenStream {
+    let mut warnings = TokenStream::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        // the attribute first putting the field in a view some way, as spelled on the field
+        let mut seen = HashMap::new();
+        // the malformed attributes are reported by `view_type_fields`
+        for_ch! {
+            for attr in &field.attrs;
+            for (share, FieldView { view, .. }) in field_views_of_attr(attr).unwrap_or_default();
+            let spelling = attr.path.get_ident().map(ident_name).unwrap_or_default();
+            if let Some(first) = seen.insert((view.to_string(), share.attr_name()), spelling.clone());
+            let member = field_member(index, field);
+            let message = if first == spelling {
+                format!(
+                    "field `{}` is put in view `{view}` by `{spelling}` more than once",
+                    quote::quote!(#member)
+                )
+            } else {
+                format!(
+                    "field `{}` is put in view `{view}` by `{spelling}`, as by `{first}` already",
+                    quote::quote!(#member)
+                )
+            };
+            warnings.extend(construct_warning(view.span(), "redundant_view_attribute", &message));
+        }
+    }
+
+    warnings
+}
+
+/// Whether the struct is `#[allow(deprecated)]`. The warnings of the derive are deprecations of
+/// items next to the struct, out of the reach of its attributes, so they're left out instead.
+/// Allowing `deprecated` is the only way to do so, and it also allows the deprecated items the
+/// struct itself uses: there is no allowing the warnings of the derive alone.
+fn allows_deprecated(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.is_ident("allow")
+            && attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .is_ok_and(|lints| lints.iter().any(|lint| lint.is_ident("deprecated")))
+    })
+}
+
+/// Emits a warning at `span` on stable, by using a deprecated item there.
+fn construct_warning(span: Span, lint: &str, message: &str) -> TokenStream {
+    let item = Ident::new(lint, span);
+    quote::quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #item: () = ();
+            #item
+        };
+    }
+}
+
+fn field_member(index: usize, field: &syn::Field) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        // a tuple field has no name to point at, so it's spanned by its type
+        None => syn::Member::Unnamed(syn::Index {
+            index: index as u32,
+            span: field.ty.span(),
+        }),
+    }
+}
+
+/// The `T` of a field type spelled `Option<T>`, for `name` `Option`, as far as it can be told
+/// from the tokens.
+fn type_argument<'t>(ty: &'t syn::Type, name: &str) -> Option<&'t syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match (segment.ident == name, args.args.first(), args.args.len()) {
+        (true, Some(syn::GenericArgument::Type(content)), 1) => Some(content),
+        _ => None,
+    }
+}
+
+fn merge_sharable(
+    prev: Option<Sharable>,
+    next: Sharable,
+    member: &syn::Member,
+    attr: &syn::Attribute,
+    view_name: &Ident,
+) -> syn::Result<Sharable> {
+    match prev {
+        Some(prev) if prev != next => Err(syn::Error::new_spanned(
+            attr,
+            format!(
+                "field `{}` is declared both `{}` and `{}` for view `{view_name}`",
+                quote::quote!(#member),
+                prev.attr_name(),
+                next.attr_name(),
+            ),
+        )),
+        _ => Ok(next),
+    }
+}
+
+/// The lifetimes of the shared and the mutable borrows of a view,
+/// each one only presents if the view has such fields.
+fn view_lifetimes(
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+) -> (Option<syn::Lifetime>, Option<syn::Lifetime>) {
+    let lifetime_of = |share: Sharable, name: &str| {
+        fields
+            .iter()
+            .any(|field| field.share == share)
+            .then(|| fresh_lifetime(name, gens))
+    };
+
+    (lifetime_of(Ref, "'__ref__"), lifetime_of(Mut, "'__mut__"))
+}
+
+/// A lifetime named `name`, with `_` appended until it doesn't collide
+/// with the lifetime parameters of the struct.
+fn fresh_lifetime(name: &str, gens: &[syn::GenericParam]) -> syn::Lifetime {
+    let mut name = name.to_owned();
+    while gens.iter().any(|param| match param {
+        syn::GenericParam::Lifetime(def) => def.lifetime.ident == name[1..],
+        _ => false,
+    }) {
+        name.push('_');
+    }
+
+    syn::Lifetime::new(&name, Span::call_site())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn construct_view_type(
+    view_name: &Ident,
+    fields: &[ViewField],
+    docs: &[syn::LitStr],
+    derives: &[syn::Path],
+    user_markers: &[syn::Type],
+    reprs: &[syn::Meta],
+    (must_use, non_exhaustive): (bool, bool),
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    // only the lifetimes the view declares appear in the marker, which keeps it covariant over them.
+    // The parameters of the struct are in too, as their uses may be reborrowed away
+    let markers = ref_lifetime
+        .iter()
+        .map(|lifetime| quote::quote!(&#lifetime ()))
+        .chain(
+            mut_lifetime
+                .iter()
+                .map(|lifetime| quote::quote!(&#lifetime mut ())),
+        )
+        .chain(gens.iter().filter_map(|param| match param {
+            syn::GenericParam::Lifetime(def) => {
+                let lifetime = &def.lifetime;
+                Some(quote::quote!(&#lifetime ()))
+            }
+            syn::GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                Some(quote::quote!(fn() -> *const #ident))
+            }
+            syn::GenericParam::Const(_) => None,
+        }))
+        .chain(user_markers.iter().map(|marker| quote::quote!(#marker)))
+        .collect::<Vec<_>>();
+
+    // the user's docs, followed by a paragraph listing the borrowed fields
+    let borrows = fields
+        .iter()
+        .map(|ViewField { ident, share, .. }| match share {
+            Ref => format!("`{ident}` shared"),
+            Mut => format!("`{ident}` mutably"),
+            Copied => format!("`{ident}` by copy"),
+            Cloned => format!("`{ident}` by clone"),
+        })
+        .collect::<Vec<_>>();
+    let summary = match &borrows[..] {
+        [] => "A view borrowing no field.".to_owned(),
+        [borrows @ .., last] if !borrows.is_empty() => {
+            format!("A view borrowing {} and {last}.", borrows.join(", "))
+        }
+        borrows => format!("A view borrowing {}.", borrows.join(", ")),
+    };
+    let separator = (!docs.is_empty()).then(|| quote::quote!(#[doc = ""]));
+    let lifetimes_doc = (ref_lifetime.is_some() && mut_lifetime.is_some()).then(|| {
+        quote::quote! {
+            #[doc = ""]
+            #[doc = "Its first lifetime is the one of its shared borrows, the second the one of its mutable borrows."]
+        }
+    });
+
+    let has_clones = fields.iter().any(|field| field.share == Cloned);
+    // requiring the copied types to be `Copy` on the struct reports a non-`Copy` one at the field,
+    // likewise for the cloned ones
+    let copied_types = fields
+        .iter()
+        .filter(|field| field.cfgs.is_empty())
+        .filter_map(
+            |ViewField { ty, share, .. }| -> Option<syn::WherePredicate> {
+                match share {
+                    Copied => {
+                        Some(syn::parse_quote_spanned!(ty.span()=> #ty: ::core::marker::Copy))
+                    }
+                    Cloned => {
+                        Some(syn::parse_quote_spanned!(ty.span()=> #ty: ::core::clone::Clone))
+                    }
+                    Ref | Mut => None,
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+    let struct_where_clause = if copied_types.is_empty() {
+        where_clause.clone()
+    } else {
+        Some(extend_where_clause(where_clause, copied_types))
+    };
+
+    let fields = fields
+        .iter()
+        .map(
+            |ViewField {
+                 ident,
+                 vis,
+                 docs: field_docs,
+                 forwarded,
+                 share,
+                 ty,
+                 wrap,
+                 ..
+             }| match (share, wrap) {
+                (Ref, _) => quote::quote! {
+                    #(#field_docs)*
+                    #(#forwarded)*
+                    #vis #ident: &#ref_lifetime #ty
+                },
+                (Mut, Some(wrap)) => quote::quote! {
+                    #(#field_docs)*
+                    #(#forwarded)*
+                    #vis #ident: #wrap<&#mut_lifetime mut #ty>
+                },
+                (Mut, None) => quote::quote! {
+                    #(#field_docs)*
+                    #(#forwarded)*
+                    #vis #ident: &#mut_lifetime mut #ty
+                },
+                (Copied | Cloned, _) => quote::quote! {
+                    #(#field_docs)*
+                    #(#forwarded)*
+                    #vis #ident: #ty
+                },
+            },
+        )
+        .zip(fields)
+        .map(with_cfgs)
+        .collect::<Vec<_>>();
+
+    // a view of shared borrows only is a bundle of `&T`s, so it's always `Copy`,
+    // which `#[derive]` can't express without bounding all the generic parameters
+    let is_copy = mut_lifetime.is_none() && !has_clones;
+    let derives = derives
+        .iter()
+        .filter(|path| !(is_copy && (path.is_ident("Clone") || path.is_ident("Copy"))));
+    let impl_gens = strip_generics_defaults(gens);
+    let copy_impls = is_copy.then(|| {
+        quote::quote! {
+            impl < #(#lifetimes,)* #(#impl_gens,)* > ::core::clone::Clone
+            for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+            #struct_where_clause
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl < #(#lifetimes,)* #(#impl_gens,)* > ::core::marker::Copy
+            for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+            #struct_where_clause
+            {
+            }
+        }
+    });
+
+    let must_use = must_use.then(|| {
+        let reason = if mut_lifetime.is_some() {
+            "this view borrows fields mutably; use it or the borrow is wasted"
+        } else {
+            "this view borrows fields; use it or the borrow is wasted"
+        };
+        quote::quote!(#[must_use = #reason])
+    });
+    let non_exhaustive = non_exhaustive.then(|| quote::quote!(#[non_exhaustive]));
+    let reprs = (!reprs.is_empty()).then(|| quote::quote!(#[repr(#(#reprs),*)]));
+
+    quote::quote! {
+        #(#[doc = #docs])*
+        #separator
+        #[doc = #summary]
+        #lifetimes_doc
+        #must_use
+        #non_exhaustive
+        #reprs
+        #[derive(#(#derives),*)]
+        // a view needn't be used, nor all its fields read, in every configuration of the crate
+        #[allow(non_camel_case_types, dead_code)]
+        #vis struct #view_name <#(#lifetimes,)* #(#gens,)*>
+        #struct_where_clause
+        {
+            #(#fields,)*
+
+            // as visible as the view, so that the ctor macro works wherever the view is named
+            #[doc(hidden)]
+            #vis __viu_marker: ::core::marker::PhantomData<(#(#markers,)*)>,
+        }
+
+        #copy_impls
+    }
+}
+
+fn construct_view_type_impl(
+    view_name: &Ident,
+    fields: &[ViewField],
+    reborrow: Option<&Ident>,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let brw = fresh_lifetime("'__brw__", gens);
+    let brw_lifetime = mut_lifetime.as_ref().map(|_| brw.clone());
+    let reborrowed_lifetimes = ref_lifetime.iter().chain(&brw_lifetime);
+
+    let mut projections = Vec::new();
+    for_ch! {
+        for ViewField { ident, share, projections: targets, cfgs, .. } in fields;
+        for target in targets;
+        let target_name = &target.segments.last().unwrap().ident;
+        let target_method = syn::Ident::new(
+            &format!("as_{}", to_snake_case(&ident_name(target_name))),
+            target_name.span(),
+        );
+        let method_name = syn::Ident::new(&format!("{}_{target_method}", ident_name(ident)), target_name.span());
+        let receiver = match share {
+            Ref => quote::quote!(&self),
+            Mut | Copied | Cloned => quote::quote!(&mut self),
+        };
+        let doc = format!("Projects the field `{ident}` into its view [`{target_name}`].");
+        projections.push(quote::quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            #[inline]
+            pub fn #method_name(#receiver) -> #target {
+                self.#ident.#target_method()
+            }
+        });
+    }
+
+    // a transposed field gets its accessor even when it's not private
+    let accessors = fields
+        .iter()
+        .filter_map(|field| {
+            let vis = field.accessor_vis.as_ref();
+            Some((
+                field,
+                vis.or(field.transposed.as_ref().map(|_| &field.vis))?,
+            ))
+        })
+        .map(
+            |(
+                ViewField {
+                    ident,
+                    share,
+                    ty,
+                    cfgs,
+                    transposed,
+                    ..
+                },
+                vis,
+            )| {
+                let ident_mut = Ident::new(&format!("{}_mut", ident_name(ident)), ident.span());
+                match (share, transposed) {
+                    (Ref, Some(content)) => quote::quote! {
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident(&self) -> ::core::option::Option<&#ref_lifetime #content> {
+                            self.#ident.as_ref()
+                        }
+                    },
+                    (Ref, _) => quote::quote! {
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident(&self) -> &#ref_lifetime #ty {
+                            self.#ident
+                        }
+                    },
+                    (Mut, _) => quote::quote! {
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident(&self) -> &#ty {
+                            &*self.#ident
+                        }
+
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident_mut(&mut self) -> &mut #ty {
+                            &mut *self.#ident
+                        }
+                    },
+                    (Copied, _) => quote::quote! {
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident(&self) -> #ty {
+                            self.#ident
+                        }
+                    },
+                    (Cloned, _) => quote::quote! {
+                        #(#cfgs)*
+                        #[inline]
+                        #vis fn #ident(&self) -> &#ty {
+                            &self.#ident
+                        }
+                    },
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+
+    // a shared `RefCell` field of `borrow_in` is borrowed mutably out of the view
+    let cell_accessors = fields.iter().filter_map(|field| {
+        let ViewField { ident, cfgs, .. } = field;
+        let content = field.cell.as_ref()?;
+        let vis = field.accessor_vis.as_ref().unwrap_or(&field.vis);
+        let ident_mut = Ident::new(&format!("{}_mut", ident_name(ident)), ident.span());
+        let doc = format!(
+            "Mutably borrows the content of `{ident}`, panicking if it's already borrowed."
+        );
+        Some(quote::quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            #[inline]
+            #[track_caller]
+            #vis fn #ident_mut(&self) -> ::core::cell::RefMut<#ref_lifetime, #content> {
+                self.#ident.borrow_mut()
+            }
+        })
+    });
+
+    let fields = fields
+        .iter()
+        .map(|field @ ViewField { ident, share, .. }| match share {
+            // copied, as `&self.x` relies on a deref coercion not tried for a `dyn Trait` field
+            Ref => quote::quote! {
+                #ident: self . #ident
+            },
+            Mut => {
+                let borrow = wrap_borrow(field, quote::quote!(&mut *self.#ident));
+                quote::quote!(#ident: #borrow)
+            }
+            Copied => quote::quote! {
+                #ident: self . #ident
+            },
+            Cloned => quote::quote! {
+                #ident: ::core::clone::Clone::clone(&self . #ident)
+            },
+        })
+        .zip(fields)
+        .map(with_cfgs)
+        .collect::<Vec<_>>();
+
+    let the_reborrow = reborrow.map(|reborrow| {
+        quote::quote! {
+            #[inline]
+            pub fn #reborrow<#brw>(&#brw mut self) -> #view_name < #(#reborrowed_lifetimes,)* #(#gens_without_bounds,)* > {
+                #view_name {
+                    #(#fields,)*
+                    __viu_marker : ::core::marker::PhantomData,
+                }
+            }
+        }
+    });
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* >
+        #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #the_reborrow
+
+            #(#projections)*
+
+            #(#accessors)*
+
+            #(#cell_accessors)*
+        }
+    }
+}
+
+/// Generates `Foo::as_shared`, which borrows every field of the view shared.
+/// A view with mutable fields gets the companion view `FooRef` for it.
+#[allow(clippy::too_many_arguments)]
+fn construct_view_type_shared(
+    struct_name: &Ident,
+    view_name: &Ident,
+    fields: &[ViewField],
+    views: &BTreeMap<Ident, Vec<ViewField>>,
+    pair: Option<&ViewPair>,
+    (markers, non_exhaustive): (&[syn::Type], bool),
+    reborrow: Option<&Ident>,
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let impl_gens = strip_generics_defaults(gens);
+    let doc = "Reborrows every field of the view shared, for as long as `self` is borrowed.";
+    let shared = fresh_lifetime("'__shared__", gens);
+
+    if let (None, Some(ViewPair { view, .. })) = (&mut_lifetime, pair) {
+        return Err(syn::Error::new(
+            view.span(),
+            format!("view `{view}` only borrows shared, it is read-only already"),
+        ));
+    }
+
+    // the clones of a view are borrowed by its companion, which keeps it `Copy`
+    let has_clones = fields.iter().any(|field| field.share == Cloned);
+    if mut_lifetime.is_none() && !has_clones {
+        return Ok(quote::quote! {
+            impl < #(#lifetimes,)* #(#impl_gens,)* >
+            #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+            #where_clause
+            {
+                #[doc = #doc]
+                #[inline]
+                pub fn as_shared(&self) -> Self {
+                    *self
+                }
+            }
+        });
+    }
+
+    let shared_name = match pair {
+        Some(ViewPair {
+            companion: Some(companion),
+            ..
+        }) => companion.clone(),
+        _ => Ident::new(&format!("{}Ref", ident_name(view_name)), view_name.span()),
+    };
+    if views.contains_key(&shared_name) {
+        return Err(syn::Error::new(
+            shared_name.span(),
+            format!(
+                "view `{shared_name}` collides with the shared companion of view `{view_name}`"
+            ),
+        ));
+    }
+    let shared_fields = fields
+        .iter()
+        .map(|field| ViewField {
+            share: match field.share {
+                Mut | Cloned => Ref,
+                share => share,
+            },
+            projections: Vec::new(),
+            // the companion derives nothing the attributes could be meant for
+            forwarded: Vec::new(),
+            wrap: None,
+            ..field.clone()
+        })
+        .collect::<Vec<_>>();
+    let the_struct = construct_view_type(
+        &shared_name,
+        &shared_fields,
+        &[],
+        &[],
+        markers,
+        &[],
+        (false, non_exhaustive),
+        vis,
+        gens,
+        gens_without_bounds,
+        where_clause,
+    );
+    // for the accessors, if any
+    let the_impl = construct_view_type_impl(
+        &shared_name,
+        &shared_fields,
+        reborrow,
+        &impl_gens,
+        gens_without_bounds,
+        where_clause,
+    );
+    let the_view_impl = construct_view_trait_impl(
+        struct_name,
+        &shared_name,
+        &shared_fields,
+        &impl_gens,
+        gens_without_bounds,
+        where_clause,
+    );
+
+    let fields = fields
+        .iter()
+        .map(|ViewField { ident, share, .. }| match share {
+            Ref | Copied => quote::quote! {
+                #ident: self . #ident
+            },
+            Mut => quote::quote! {
+                #ident: & * self . #ident
+            },
+            Cloned => quote::quote! {
+                #ident: & self . #ident
+            },
+        })
+        .zip(fields)
+        .map(with_cfgs)
+        .collect::<Vec<_>>();
+
+    // a paired view can also be borrowed read-only out of the struct right away
+    let the_pair_method = pair.map(|_| {
+        let method_name = Ident::new(
+            &format!("as_{}", to_snake_case(&ident_name(&shared_name))),
+            shared_name.span(),
+        );
+        let method_doc =
+            format!("Borrows the fields of view [`{view_name}`] out of `self`, read-only.");
+        let struct_fields = borrow_view_fields(&quote::quote!(self), &shared_fields);
+        quote::quote! {
+            impl < #(#impl_gens,)* > #struct_name < #(#gens_without_bounds,)* >
+            #where_clause
+            {
+                #[doc = #method_doc]
+                #[inline]
+                #[track_caller]
+                #vis fn #method_name(&self) -> #shared_name < '_, #(#gens_without_bounds,)* > {
+                    #shared_name {
+                        #(#struct_fields,)*
+                        __viu_marker : ::core::marker::PhantomData,
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote::quote! {
+        #the_struct
+        #the_impl
+        #the_view_impl
+        #the_pair_method
+
+        impl < #(#lifetimes,)* #(#impl_gens,)* >
+        #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[doc = #doc]
+            #[inline]
+            pub fn as_shared(&self) -> #shared_name < '_, #(#gens_without_bounds,)* > {
+                #shared_name {
+                    #(#fields,)*
+                    __viu_marker : ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        // not `Deref`, whose target would outlive the borrow of the view: the companion is `Copy`,
+        // it could be copied out and read while the view writes again
+        impl < #shared, #(#lifetimes,)* #(#impl_gens,)* >
+        ::core::convert::From<&#shared #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >>
+        for #shared_name < #shared, #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[inline]
+            fn from(view: &#shared #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >) -> Self {
+                view.as_shared()
+            }
+        }
+    })
+}
+
+/// Generates `FooBuilder`, which wraps the view `Foo` to set its mutable fields
+/// one after another.
+fn construct_view_builder(
+    view_name: &Ident,
+    fields: &[ViewField],
+    views: &BTreeMap<Ident, Vec<ViewField>>,
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let builder_name = Ident::new(
+        &format!("{}Builder", ident_name(view_name)),
+        view_name.span(),
+    );
+    if views.contains_key(&builder_name) {
+        return Err(syn::Error::new(
+            builder_name.span(),
+            format!("view `{builder_name}` collides with the builder of view `{view_name}`"),
+        ));
+    }
+
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let impl_gens = strip_generics_defaults(gens);
+    let doc = format!("Sets the mutable fields of the view [`{view_name}`] one after another.");
+
+    let setters = fields
+        .iter()
+        .filter(|field| field.share == Mut)
+        .map(
+            |ViewField {
+                 ident,
+                 ty,
+                 cfgs,
+                 wrap,
+                 ..
+             }| {
+                let setter = Ident::new(&format!("with_{}", ident_name(ident)), ident.span());
+                let doc = format!("Sets `{ident}` through the view.");
+                // a wrapped field is written through `DerefMut` of its wrapper
+                let mutability = wrap.is_some().then(|| quote::quote!(mut));
+                quote::quote! {
+                    #(#cfgs)*
+                    #[doc = #doc]
+                    #[inline]
+                    #[track_caller]
+                    pub fn #setter(#mutability self, #ident: #ty) -> Self {
+                        *self.view.#ident = #ident;
+                        self
+                    }
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+
+    Ok(quote::quote! {
+        #[doc = #doc]
+        #[allow(non_camel_case_types, dead_code)]
+        #vis struct #builder_name < #(#lifetimes,)* #(#gens,)* >
+        #where_clause
+        {
+            view: #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >,
+        }
+
+        impl < #(#lifetimes,)* #(#impl_gens,)* >
+        #builder_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            /// Starts setting the fields of `view`.
+            #[inline]
+            #[track_caller]
+            pub fn new(view: #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >) -> Self {
+                Self { view }
+            }
+
+            #(#setters)*
+
+            /// Gives the view back.
+            #[inline]
+            #[track_caller]
+            pub fn done(self) -> #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* > {
+                self.view
+            }
+        }
+    })
+}
+
+/// Implements `viu::View` for the view `view_name` of the struct `struct_name`.
+fn construct_view_trait_impl(
+    struct_name: &Ident,
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let name = ident_name(view_name);
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* > ::viu::View
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            type Source = #struct_name < #(#gens_without_bounds,)* >;
+
+            const NAME: &'static str = #name;
+        }
+    }
+}
+
+/// Checks that the view `Foo` implements the traits of `#[view_assert(Foo: Send)]`, for any
+/// arguments the struct accepts.
+fn construct_view_assert(
+    view_name: &Ident,
+    fields: &[ViewField],
+    traits: &[syn::Path],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    // the error of an unsatisfied trait points at it in the attribute
+    let asserts = traits.iter().map(|path| {
+        quote::quote_spanned! {path.span()=>
+            fn assert_impl<T: ?::core::marker::Sized + #path>(_: &T) {}
+            assert_impl(&view);
+        }
+    });
+
+    quote::quote! {
+        const _: () = {
+            // the view is taken as an argument, whose type is then known to be well-formed
+            #[allow(dead_code)]
+            fn assert_view_traits< #(#lifetimes,)* #(#gens,)* >(
+                view: #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >,
+            )
+            #where_clause
+            {
+                #({ #asserts })*
+            }
+        };
+    }
+}
+
+fn construct_view_type_method(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let method_name = syn::Ident::new(
+        &format!("as_{}", to_snake_case(&ident_name(view_name))),
+        view_name.span(),
+    );
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let elided_lifetimes = ref_lifetime
+        .iter()
+        .chain(&mut_lifetime)
+        .map(|_| syn::Lifetime::new("'_", Span::call_site()));
+    let receiver = if mut_lifetime.is_some() {
+        quote::quote!(&mut self)
+    } else {
+        quote::quote!(&self)
+    };
+    let doc = format!("Borrows the fields of view [`{view_name}`] out of `self`.");
+    let fields = borrow_view_fields(&quote::quote!(self), fields);
+    let view = quote::quote! {
+        #view_name {
+            #(#fields,)*
+            __viu_marker : ::core::marker::PhantomData,
+        }
+    };
+
+    let (unsafety, safety_doc, body) = if is_union {
+        (
+            Some(quote::quote!(unsafe)),
+            Some(quote::quote! {
+                #[doc = ""]
+                #[doc = "# Safety"]
+                #[doc = ""]
+                #[doc = "The borrowed fields of the union must hold valid values of their types."]
+            }),
+            quote::quote!(unsafe { #view }),
+        )
+    } else {
+        (None, None, view)
+    };
+
+    quote::quote! {
+        impl < #(#gens,)* > #struct_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[doc = #doc]
+            #safety_doc
+            #[inline]
+            #[track_caller]
+            #vis #unsafety fn #method_name(#receiver) -> #view_name < #(#elided_lifetimes,)* #(#gens_without_bounds,)* > {
+                #body
+            }
+        }
+    }
+}
+
+/// Generates the methods of `#[view_delegate(Foo: fn tick(&mut self))]` on the struct, which borrow
+/// the view `Foo` out of `self` and call the method of the same name the user gave the view.
+#[allow(clippy::too_many_arguments)]
+fn construct_view_delegates(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    sigs: &[syn::Signature],
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    if is_union {
+        return Err(syn::Error::new(
+            view_name.span(),
+            format!(
+                "`{VIEW_DELEGATE}` can't be used on a union, whose views are borrowed unsafely"
+            ),
+        ));
+    }
+    let as_view = syn::Ident::new(
+        &format!("as_{}", to_snake_case(&ident_name(view_name))),
+        view_name.span(),
+    );
+    let (_, mut_lifetime) = view_lifetimes(fields, gens);
+
+    let mut methods = Vec::new();
+    for sig in sigs {
+        let name = &sig.ident;
+        let mut inputs = sig.inputs.iter();
+        match inputs.next() {
+            Some(syn::FnArg::Receiver(syn::Receiver {
+                reference: Some(_),
+                mutability,
+                ..
+            })) if mutability.is_some() || mut_lifetime.is_none() => {}
+            Some(syn::FnArg::Receiver(syn::Receiver {
+                reference: Some(_),
+                ..
+            })) => {
+                return Err(syn::Error::new_spanned(
+                    &sig.inputs,
+                    format!("view `{view_name}` borrows fields mutably, `{name}` must take `&mut self`"),
+                ))
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("`{name}` must take `&self` or `&mut self`, to borrow view `{view_name}` out of it"),
+                ))
+            }
+        }
+        let args = inputs
+            .map(|input| match input {
+                syn::FnArg::Typed(syn::PatType { pat, .. }) => match &**pat {
+                    syn::Pat::Ident(syn::PatIdent {
+                        ident,
+                        by_ref: None,
+                        subpat: None,
+                        ..
+                    }) => Ok(ident),
+                    pat => Err(syn::Error::new_spanned(
+                        pat,
+                        "the arguments of a delegated method must be named, to be passed on",
+                    )),
+                },
+                syn::FnArg::Receiver(receiver) => {
+                    Err(syn::Error::new_spanned(receiver, "unexpected receiver"))
+                }
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        // the arguments are only passed on, a `mut` on them would be unused
+        let mut sig = sig.clone();
+        for_ch! {
+            for input in &mut sig.inputs;
+            if let syn::FnArg::Typed(syn::PatType { pat, .. }) = input;
+            if let syn::Pat::Ident(pat) = &mut **pat;
+            pat.mutability = None;
+        }
+        let doc = format!("Borrows the view [`{view_name}`] out of `self` and calls [`{view_name}::{name}`] on it.");
+        methods.push(quote::quote! {
+            #[doc = #doc]
+            #[inline]
+            #vis #sig {
+                self.#as_view().#name(#(#args),*)
+            }
+        });
+    }
+
+    Ok(quote::quote! {
+        impl < #(#gens,)* > #struct_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #(#methods)*
+        }
+    })
+}
+
+/// Generates the trait `AsFoo` of the method `as_foo`, implemented by the struct,
+/// so that generic code can be bounded on being able to borrow the view `Foo`.
+#[allow(clippy::too_many_arguments)]
+fn construct_view_type_trait(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    foreign: bool,
+    views: &BTreeMap<Ident, Vec<ViewField>>,
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let trait_name = Ident::new(&format!("As{}", ident_name(view_name)), view_name.span());
+    if views.contains_key(&trait_name) {
+        return Err(syn::Error::new(
+            trait_name.span(),
+            format!("view `{trait_name}` collides with the trait of view `{view_name}`"),
+        ));
+    }
+
+    let method_name = syn::Ident::new(
+        &format!("as_{}", to_snake_case(&ident_name(view_name))),
+        view_name.span(),
+    );
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let elided_lifetimes = ref_lifetime
+        .iter()
+        .chain(&mut_lifetime)
+        .map(|_| syn::Lifetime::new("'_", Span::call_site()))
+        .collect::<Vec<_>>();
+    let receiver = if mut_lifetime.is_some() {
+        quote::quote!(&mut self)
+    } else {
+        quote::quote!(&self)
+    };
+    let impl_gens = strip_generics_defaults(gens);
+    let trait_doc = format!("Types the view [`{view_name}`] can be borrowed out of.");
+    let method_doc = format!("Borrows the fields of view [`{view_name}`] out of `self`.");
+
+    let (unsafety, safety_doc, body) = if is_union {
+        (
+            Some(quote::quote!(unsafe)),
+            Some(quote::quote! {
+                #[doc = ""]
+                #[doc = "# Safety"]
+                #[doc = ""]
+                #[doc = "The borrowed fields of the union must hold valid values of their types."]
+            }),
+            quote::quote!(unsafe { #struct_name::#method_name(self) }),
+        )
+    } else if foreign {
+        let fields = borrow_view_fields(&quote::quote!(self), fields);
+        let view = quote::quote! {
+            #view_name {
+                #(#fields,)*
+                __viu_marker : ::core::marker::PhantomData,
+            }
+        };
+        (None, None, view)
+    } else {
+        (None, None, quote::quote!(#struct_name::#method_name(self)))
+    };
+
+    Ok(quote::quote! {
+        #[doc = #trait_doc]
+        // nothing may bound on it, which is warned for structs generated by a `macro_rules!`
+        #[allow(non_camel_case_types, dead_code)]
+        #vis trait #trait_name < #(#gens,)* > #where_clause {
+            #[doc = #method_doc]
+            #safety_doc
+            #unsafety fn #method_name(#receiver) -> #view_name < #(#elided_lifetimes,)* #(#gens_without_bounds,)* >;
+        }
+
+        impl < #(#impl_gens,)* > #trait_name < #(#gens_without_bounds,)* >
+        for #struct_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[inline]
+            #[track_caller]
+            #unsafety fn #method_name(#receiver) -> #view_name < #(#elided_lifetimes,)* #(#gens_without_bounds,)* > {
+                // the inherent method, if any, takes precedence over this one
+                #body
+            }
+        }
+    })
+}
+
+/// Generates `From<&Struct>` for a view of `ref_in` fields only, or `From<&mut Struct>`
+/// for a view of `mut_in` fields only. A mixed view needs both borrows so it gets none.
+fn construct_view_type_from(
+    struct_name: &Ident,
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> Option<TokenStream> {
+    let method_name = syn::Ident::new(
+        &format!("as_{}", to_snake_case(&ident_name(view_name))),
+        view_name.span(),
+    );
+    let (lifetime, reference) = match view_lifetimes(fields, gens) {
+        (Some(lifetime), None) => {
+            let reference = quote::quote!(&#lifetime);
+            (lifetime, reference)
+        }
+        (None, Some(lifetime)) => {
+            let reference = quote::quote!(&#lifetime mut);
+            (lifetime, reference)
+        }
+        _ => return None,
+    };
+
+    Some(quote::quote! {
+        impl < #lifetime, #(#gens,)* >
+        ::core::convert::From< #reference #struct_name < #(#gens_without_bounds,)* > >
+        for #view_name < #lifetime, #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[inline]
+            fn from(value: #reference #struct_name < #(#gens_without_bounds,)* >) -> Self {
+                value.#method_name()
+            }
+        }
+    })
+}
+
+/// Generates `split_left_right`, which borrows two disjoint views out of the struct at once.
+/// Checks that the two views can be borrowed at once: a field is only in both
+/// if neither borrows it mutably.
+fn check_disjoint_views(
+    (left_name, left_fields): (&Ident, &[ViewField]),
+    (right_name, right_fields): (&Ident, &[ViewField]),
+) -> syn::Result<()> {
+    for_ch! {
+        for left_field in left_fields;
+        for right_field in right_fields;
+        if left_field.member == right_field.member;
+        if left_field.share == Mut || right_field.share == Mut {
+            let ident = &left_field.ident;
+            let (mut_view, other_view) = if left_field.share == Mut {
+                (left_name, right_name)
+            } else {
+                (right_name, left_name)
+            };
+            return Err(syn::Error::new(
+                right_name.span(),
+                format!(
+                    "views `{left_name}` and `{right_name}` overlap: field `{ident}` is borrowed mutably by `{mut_view}` and also borrowed by `{other_view}`"
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn construct_view_split(
+    struct_name: &Ident,
+    (left_name, left_fields): (&Ident, &[ViewField]),
+    (right_name, right_fields): (&Ident, &[ViewField]),
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    check_disjoint_views((left_name, left_fields), (right_name, right_fields))?;
+
+    let method_name = syn::Ident::new(
+        &format!(
+            "split_{}_{}",
+            to_snake_case(&ident_name(left_name)),
+            to_snake_case(&ident_name(right_name))
+        ),
+        right_name.span(),
+    );
+    let elided_lifetimes = |fields| {
+        let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+        ref_lifetime
+            .iter()
+            .chain(&mut_lifetime)
+            .map(|_| syn::Lifetime::new("'_", Span::call_site()))
+            .collect::<Vec<_>>()
+    };
+    let left_lifetimes = elided_lifetimes(left_fields);
+    let right_lifetimes = elided_lifetimes(right_fields);
+    let receiver = if left_fields
+        .iter()
+        .chain(right_fields)
+        .any(|field| field.share == Mut)
+    {
+        quote::quote!(&mut self)
+    } else {
+        quote::quote!(&self)
+    };
+    let doc = format!(
+        "Borrows the fields of the views [`{left_name}`] and [`{right_name}`] out of `self` at once."
+    );
+    let left = borrow_view_fields(&quote::quote!(self), left_fields);
+    let right = borrow_view_fields(&quote::quote!(self), right_fields);
+
+    Ok(quote::quote! {
+        impl < #(#gens,)* > #struct_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[doc = #doc]
+            #[inline]
+            #[track_caller]
+            #vis fn #method_name(#receiver) -> (
+                #left_name < #(#left_lifetimes,)* #(#gens_without_bounds,)* >,
+                #right_name < #(#right_lifetimes,)* #(#gens_without_bounds,)* >,
+            ) {
+                (
+                    #left_name {
+                        #(#left,)*
+                        __viu_marker : ::core::marker::PhantomData,
+                    },
+                    #right_name {
+                        #(#right,)*
+                        __viu_marker : ::core::marker::PhantomData,
+                    },
+                )
+            }
+        }
+    })
+}
+
+/// Generates `split_g(&mut self)` for `#[split_group(G: a, b)]`, or `split_ref_g(&self)` for
+/// `#[ref_split(G: a, b)]` when `shared`.
+fn construct_split_group(
+    struct_name: &Ident,
+    (group, shared): (&Ident, bool),
+    members: &[(syn::Member, syn::Type)],
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let prefix = if shared { "split_ref" } else { "split" };
+    let method_name = syn::Ident::new(
+        &format!("{prefix}_{}", to_snake_case(&ident_name(group))),
+        group.span(),
+    );
+    let (receiver, borrow, how) = if shared {
+        (quote::quote!(&self), quote::quote!(&), "shared")
+    } else {
+        (quote::quote!(&mut self), quote::quote!(&mut), "mutably")
+    };
+    let doc = format!(
+        "Borrows the fields {} out of `self` {how} at once.",
+        members
+            .iter()
+            .map(|(member, _)| format!("`{}`", quote::quote!(#member)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let (members, tys): (Vec<_>, Vec<_>) = members.iter().cloned().unzip();
+
+    quote::quote! {
+        impl < #(#gens,)* > #struct_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[doc = #doc]
+            #[inline]
+            #[track_caller]
+            #vis fn #method_name(#receiver) -> ( #(#borrow #tys,)* ) {
+                ( #(#borrow self.#members,)* )
+            }
+        }
+    }
+}
+
+/// Puts the `#[cfg]`s of a field on the tokens generated for it.
+fn with_cfgs((tokens, field): (TokenStream, &ViewField)) -> TokenStream {
+    let cfgs = &field.cfgs;
+    quote::quote! {
+        #(#cfgs)*
+        #tokens
+    }
+}
+
+/// Wraps the borrow of a field into its `view_wrap` type, if any.
+fn wrap_borrow(field: &ViewField, borrow: TokenStream) -> TokenStream {
+    match &field.wrap {
+        Some(wrap) => quote::quote!(#wrap::new(#borrow)),
+        None => borrow,
+    }
+}
+
+/// The initializers of the view fields, borrowing them out of `src`.
+fn borrow_view_fields(src: &TokenStream, fields: &[ViewField]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(
+            |field @ ViewField {
+                 member,
+                 ident,
+                 share,
+                 deref,
+                 ..
+             }| {
+                let deref = deref.then(|| quote::quote!(*));
+                match share {
+                    Ref => quote::quote! {
+                        #ident: & #deref #src . #member
+                    },
+                    Mut => {
+                        let borrow = wrap_borrow(field, quote::quote!(&mut #deref #src . #member));
+                        quote::quote!(#ident: #borrow)
+                    }
+                    Copied => quote::quote! {
+                        #ident: #deref #src . #member
+                    },
+                    Cloned => quote::quote! {
+                        #ident: ::core::clone::Clone::clone(& #deref #src . #member)
+                    },
+                }
+            },
+        )
+        .zip(fields)
+        .map(with_cfgs)
+        .collect()
+}
+
+/// The name of an identifier without the `r#` of a raw one, to derive other names from it.
+fn ident_name(ident: &Ident) -> String {
+    ident.unraw().to_string()
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut res = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let word_start = match prev {
+                Some(prev) => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase() && next.is_some_and(|c| c.is_lowercase()))
+                }
+                None => false,
+            };
+            if word_start && !res.ends_with('_') {
+                res.push('_');
+            }
+            res.extend(c.to_lowercase());
+        } else {
+            res.push(c);
+        }
+    }
+
+    res
+}
+
+/// Generates `Big::to_small`, which reborrows the fields of the view `Small` out of `Big`,
+/// or `Big::narrow_to_small` and `From<&mut Big> for Small` for `narrow`.
+fn construct_view_subset(
+    (small_name, small_fields): (&Ident, &[ViewField]),
+    (big_name, big_fields): (&Ident, &[ViewField]),
+    narrow: bool,
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let mut fields = Vec::new();
+    let mut reborrows_ref = false;
+    for small_field in small_fields {
+        let ident = &small_field.ident;
+        let big_field = big_fields
+            .iter()
+            .find(|big_field| big_field.member == small_field.member)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    small_name.span(),
+                    format!("field `{ident}` of view `{small_name}` is not in view `{big_name}`"),
+                )
+            })?;
+
+        // the field may be named differently in both views
+        let big_ident = &big_field.ident;
+        let init = match (small_field.share, big_field.share) {
+            (Ref, Ref) => quote::quote!(#ident: self.#big_ident),
+            (Ref, Mut) => {
+                reborrows_ref = true;
+                quote::quote!(#ident: &*self.#big_ident)
+            }
+            (Ref, Copied | Cloned) => {
+                reborrows_ref = true;
+                quote::quote!(#ident: &self.#big_ident)
+            }
+            (Copied, Copied | Cloned) => quote::quote!(#ident: self.#big_ident),
+            (Copied, Ref | Mut) => quote::quote!(#ident: *self.#big_ident),
+            (Cloned, Copied | Cloned) => {
+                quote::quote!(#ident: ::core::clone::Clone::clone(&self.#big_ident))
+            }
+            (Cloned, Ref | Mut) => {
+                quote::quote!(#ident: ::core::clone::Clone::clone(&*self.#big_ident))
+            }
+            (Mut, Mut) => {
+                let borrow = wrap_borrow(small_field, quote::quote!(&mut *self.#big_ident));
+                quote::quote!(#ident: #borrow)
+            }
+            (Mut, Ref | Copied | Cloned) => {
+                return Err(syn::Error::new(
+                    small_name.span(),
+                    format!(
+                        "field `{ident}` is mutable in view `{small_name}` but not in view `{big_name}`"
+                    ),
+                ))
+            }
+        };
+        fields.push(with_cfgs((init, small_field)));
+    }
+
+    let (big_ref_lifetime, big_mut_lifetime) = view_lifetimes(big_fields, gens);
+    let big_lifetimes = big_ref_lifetime
+        .iter()
+        .chain(&big_mut_lifetime)
+        .collect::<Vec<_>>();
+    let brw_lifetime = fresh_lifetime("'__brw__", gens);
+    // the shared borrows keep their lifetime unless some of them are reborrowed from mutable ones
+    let (small_ref_lifetime, small_mut_lifetime) = view_lifetimes(small_fields, gens);
+    let small_lifetimes = small_ref_lifetime
+        .map(|_| match &big_ref_lifetime {
+            Some(big_ref_lifetime) if !reborrows_ref => big_ref_lifetime.clone(),
+            _ => brw_lifetime.clone(),
+        })
+        .into_iter()
+        .chain(small_mut_lifetime.as_ref().map(|_| brw_lifetime.clone()))
+        .collect::<Vec<_>>();
+    let mutability = small_mut_lifetime.is_some().then(|| quote::quote!(mut));
+    let prefix = if narrow { "narrow_to" } else { "to" };
+    let method_name = syn::Ident::new(
+        &format!("{prefix}_{}", to_snake_case(&ident_name(small_name))),
+        small_name.span(),
+    );
+    let doc = format!("Reborrows the fields of view [`{small_name}`] out of `self`.");
+
+    let the_from = narrow.then(|| {
+        quote::quote! {
+            impl < #brw_lifetime, #(#big_lifetimes,)* #(#gens,)* >
+            ::core::convert::From<&#brw_lifetime #mutability #big_name < #(#big_lifetimes,)* #(#gens_without_bounds,)* >>
+            for #small_name < #(#small_lifetimes,)* #(#gens_without_bounds,)* >
+            #where_clause
+            {
+                #[inline]
+                fn from(view: &#brw_lifetime #mutability #big_name < #(#big_lifetimes,)* #(#gens_without_bounds,)* >) -> Self {
+                    view.#method_name()
+                }
+            }
+        }
+    });
+
+    Ok(quote::quote! {
+        impl < #(#big_lifetimes,)* #(#gens,)* >
+        #big_name < #(#big_lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[doc = #doc]
+            #[inline]
+            #[track_caller]
+            #vis fn #method_name<#brw_lifetime>(&#brw_lifetime #mutability self) -> #small_name < #(#small_lifetimes,)* #(#gens_without_bounds,)* > {
+                #small_name {
+                    #(#fields,)*
+                    __viu_marker : ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #the_from
+    })
+}
+
+fn construct_view_type_debug(
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    // a type of a cfg-ed out field may not exist, so it can't be bounded
+    let where_clause = extend_where_clause(
+        where_clause,
+        fields.iter().filter(|field| field.cfgs.is_empty()).map(
+            |ViewField { ty, .. }| -> syn::WherePredicate {
+                syn::parse_quote_spanned!(ty.span()=> #ty: ::core::fmt::Debug)
+            },
+        ),
+    );
+    let name = ident_name(view_name);
+
+    let fields = fields
+        .iter()
+        .map(|ViewField { ident, wrap, .. }| {
+            let name = ident_name(ident);
+            // the wrapped value, whose wrapper may not be `Debug`
+            let deref = wrap.is_some().then(|| quote::quote!(*));
+            quote::quote! {
+                debug.field(#name, &#deref self.#ident);
+            }
+        })
+        .zip(fields)
+        .map(with_cfgs)
+        .collect::<Vec<_>>();
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* > ::core::fmt::Debug
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut debug = f.debug_struct(#name);
+                #(#fields)*
+                debug.finish()
+            }
+        }
+    }
+}
+
+/// Implements `Index`, and `IndexMut` for a `mut_in` field, for a view of a single field by
+/// forwarding to it.
+fn construct_view_index(
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let field = match fields {
+        [field] => field,
+        _ => {
+            return Err(syn::Error::new(
+                view_name.span(),
+                format!(
+                    "view `{view_name}` must have a single field to be indexed, it has {}",
+                    fields.len()
+                ),
+            ))
+        }
+    };
+    let ViewField {
+        ident,
+        ty,
+        share,
+        cfgs,
+        ..
+    } = field;
+    if !maybe_indexable(ty) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            format!("field `{ident}` of view `{view_name}` can't be indexed"),
+        ));
+    }
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let index = Ident::new("__ViuIndex", Span::call_site());
+    let index_where_clause = extend_where_clause(
+        where_clause,
+        [syn::parse_quote_spanned!(ty.span()=> #ty: ::core::ops::Index<#index>)],
+    );
+    let index_mut_where_clause = extend_where_clause(
+        where_clause,
+        [syn::parse_quote_spanned!(ty.span()=> #ty: ::core::ops::IndexMut<#index>)],
+    );
+    let field_ref = match share {
+        Copied | Cloned => quote::quote!(&self.#ident),
+        Ref | Mut => quote::quote!(&*self.#ident),
+    };
+
+    let index_mut = (*share == Mut).then(|| {
+        quote::quote! {
+            #(#cfgs)*
+            impl < #(#lifetimes,)* #(#gens,)* #index > ::core::ops::IndexMut<#index>
+            for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+            #index_mut_where_clause
+            {
+                #[inline]
+                #[track_caller]
+                fn index_mut(&mut self, index: #index) -> &mut Self::Output {
+                    ::core::ops::IndexMut::index_mut(&mut *self.#ident, index)
+                }
+            }
+        }
+    });
+
+    Ok(quote::quote! {
+        #(#cfgs)*
+        impl < #(#lifetimes,)* #(#gens,)* #index > ::core::ops::Index<#index>
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #index_where_clause
+        {
+            type Output = <#ty as ::core::ops::Index<#index>>::Output;
+
+            #[inline]
+            #[track_caller]
+            fn index(&self, index: #index) -> &Self::Output {
+                ::core::ops::Index::index(#field_ref, index)
+            }
+        }
+
+        #index_mut
+    })
+}
+
+/// Whether `ty` may implement `Index`, telling apart only the types which obviously don't,
+/// the other ones are left to the `Index` bound.
+fn maybe_indexable(ty: &syn::Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+        "i128", "isize", "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            !PRIMITIVES.iter().any(|primitive| path.is_ident(primitive))
+        }
+        syn::Type::Group(group) => maybe_indexable(&group.elem),
+        syn::Type::Paren(paren) => maybe_indexable(&paren.elem),
+        syn::Type::Reference(reference) => maybe_indexable(&reference.elem),
+        syn::Type::BareFn(_) | syn::Type::Never(_) | syn::Type::Ptr(_) | syn::Type::Tuple(_) => {
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Generates `FooLens`, holding for each field of the view `Foo` a `viu::Lens` reaching it through
+/// the struct, or a `viu::Getter` when the view doesn't borrow it mutably.
+#[allow(clippy::too_many_arguments)]
+fn construct_view_lens(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    views: &BTreeMap<Ident, Vec<ViewField>>,
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    let lens_name = Ident::new(&format!("{}Lens", ident_name(view_name)), view_name.span());
+    if views.contains_key(&lens_name) {
+        return Err(syn::Error::new(
+            lens_name.span(),
+            format!("view `{lens_name}` collides with the lens of view `{view_name}`"),
+        ));
+    }
+    // the lenses reach the fields out of any view, which would be reading a union
+    if is_union {
+        return Err(syn::Error::new(
+            view_name.span(),
+            format!("`{VIEW_LENS}` is not supported on unions"),
+        ));
+    }
+
+    let impl_gens = strip_generics_defaults(gens);
+    let source = quote::quote!(#struct_name < #(#gens_without_bounds,)* >);
+    let doc = format!(
+        "The lenses of the fields of the view [`{view_name}`], reaching them through [`{struct_name}`]."
+    );
+
+    let (lens_fields, lens_inits) = fields
+        .iter()
+        .map(
+            |ViewField {
+                 member,
+                 ident,
+                 vis,
+                 share,
+                 ty,
+                 deref,
+                 ..
+             }| {
+                let deref = deref.then(|| quote::quote!(*));
+                let doc = format!("Reaches `{member}`.", member = quote::quote!(#member));
+                match share {
+                    Mut => (
+                        quote::quote!(#[doc = #doc] #vis #ident: ::viu::Lens<#source, #ty>),
+                        quote::quote! {
+                            #ident: ::viu::Lens::new(
+                                |source| & #deref source.#member,
+                                |source| &mut #deref source.#member,
+                            )
+                        },
+                    ),
+                    Ref | Copied | Cloned => (
+                        quote::quote!(#[doc = #doc] #vis #ident: ::viu::Getter<#source, #ty>),
+                        quote::quote!(#ident: ::viu::Getter::new(|source| & #deref source.#member)),
+                    ),
+                }
+            },
+        )
+        .zip(fields)
+        .map(|((lens_field, lens_init), field)| {
+            (
+                with_cfgs((lens_field, field)),
+                with_cfgs((lens_init, field)),
+            )
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    Ok(quote::quote! {
+        #[doc = #doc]
+        #[allow(non_camel_case_types, dead_code)]
+        #vis struct #lens_name < #(#gens,)* >
+        #where_clause
+        {
+            #(#lens_fields,)*
+        }
+
+        impl < #(#impl_gens,)* > #lens_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            /// The lenses of the fields of the view.
+            pub const fn new() -> Self {
+                Self {
+                    #(#lens_inits,)*
+                }
+            }
+        }
+
+        impl < #(#impl_gens,)* > ::core::clone::Clone for #lens_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl < #(#impl_gens,)* > ::core::marker::Copy for #lens_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {}
+    })
+}
+
+/// Implements `PartialEq` between views, and between a view and the struct,
+/// comparing the borrowed values.
+fn construct_view_type_eq(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let where_clause = extend_where_clause(
+        where_clause,
+        fields.iter().filter(|field| field.cfgs.is_empty()).map(
+            |ViewField { ty, .. }| -> syn::WherePredicate {
+                syn::parse_quote_spanned!(ty.span()=> #ty: ::core::cmp::PartialEq)
+            },
+        ),
+    );
+
+    let (view_cmps, struct_cmps) = fields
+        .iter()
+        .map(
+            |ViewField {
+                 member,
+                 ident,
+                 share,
+                 deref,
+                 ..
+             }| {
+                let value = matches!(share, Ref | Mut).then(|| quote::quote!(*));
+                let deref = deref.then(|| quote::quote!(*));
+                (
+                    quote::quote!(if #value self.#ident != #value other.#ident { return false; }),
+                    quote::quote!(if #value self.#ident != #deref other.#member { return false; }),
+                )
+            },
+        )
+        .zip(fields)
+        .map(|((view_cmp, struct_cmp), field)| {
+            (with_cfgs((view_cmp, field)), with_cfgs((struct_cmp, field)))
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    // comparing with a union would read its fields
+    let struct_eq = (!is_union).then(|| {
+        quote::quote! {
+            impl < #(#lifetimes,)* #(#gens,)* > ::core::cmp::PartialEq< #struct_name < #(#gens_without_bounds,)* > >
+            for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+            #where_clause
+            {
+                fn eq(&self, other: & #struct_name < #(#gens_without_bounds,)* >) -> bool {
+                    #(#struct_cmps)*
+                    true
+                }
+            }
+        }
+    });
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* > ::core::cmp::PartialEq
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            fn eq(&self, other: &Self) -> bool {
+                #(#view_cmps)*
+                true
+            }
+        }
+
+        #struct_eq
+    }
+}
+
+/// Implements `Eq` for a view whose borrowed values are `Eq`, along with the `PartialEq`
+/// comparing them.
+fn construct_view_type_total_eq(
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let where_clause = extend_where_clause(
+        where_clause,
+        fields.iter().filter(|field| field.cfgs.is_empty()).map(
+            |ViewField { ty, .. }| -> syn::WherePredicate {
+                syn::parse_quote_spanned!(ty.span()=> #ty: ::core::cmp::Eq)
+            },
+        ),
+    );
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* > ::core::cmp::Eq
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {}
+    }
+}
+
+/// Implements `Hash` for a view by hashing the borrowed values, consistently with its
+/// `PartialEq`. It's refused on a view borrowing mutably, which isn't meant to be a key.
+fn construct_view_type_hash(
+    hash: &syn::Path,
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    if let Some(field) = fields.iter().find(|field| field.share == Mut) {
+        return Err(syn::Error::new_spanned(
+            hash,
+            format!(
+                "view `{view_name}` can't derive `Hash`, it borrows field `{}` mutably",
+                field.ident
+            ),
+        ));
+    }
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    let where_clause = extend_where_clause(
+        where_clause,
+        fields.iter().filter(|field| field.cfgs.is_empty()).map(
+            |ViewField { ty, .. }| -> syn::WherePredicate {
+                syn::parse_quote_spanned!(ty.span()=> #ty: ::core::hash::Hash)
+            },
+        ),
+    );
+    let hashes = fields
+        .iter()
+        .map(|ViewField { ident, share, .. }| {
+            let borrow = (*share != Ref).then(|| quote::quote!(&));
+            quote::quote!(::core::hash::Hash::hash(#borrow self.#ident, state);)
+        })
+        .zip(fields)
+        .map(with_cfgs);
+
+    Ok(quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* > ::core::hash::Hash
+        for #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            fn hash<__ViuHasher: ::core::hash::Hasher>(&self, state: &mut __ViuHasher) {
+                #(#hashes)*
+            }
+        }
+    })
+}
+
+/// Generates `type FooIn<'a> = Foo<'a, 'a>`, naming the view `Foo` borrowing all its fields for
+/// the same lifetime, and with `args` binding the type and const parameters of the struct, like
+/// `type IntFooIn<'a> = Foo<'a, 'a, i32>`.
+fn construct_view_alias(
+    view_name: &Ident,
+    alias: &Ident,
+    args: Option<&syn::AngleBracketedGenericArguments>,
+    fields: &[ViewField],
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+) -> syn::Result<TokenStream> {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    if ref_lifetime.is_none() && mut_lifetime.is_none() {
+        return Err(syn::Error::new(
+            alias.span(),
+            format!("view `{view_name}` borrows no field, it has no lifetime to alias"),
+        ));
+    }
+    let view_args = match args {
+        None => gens_without_bounds.to_vec(),
+        Some(args) => {
+            if let Some(arg) = args.args.iter().find(|arg| {
+                !matches!(
+                    arg,
+                    syn::GenericArgument::Type(_) | syn::GenericArgument::Const(_)
+                )
+            }) {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "an alias only binds the type and const parameters of the struct",
+                ));
+            }
+            let expected = gens
+                .iter()
+                .filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+                .count();
+            if args.args.len() != expected {
+                return Err(syn::Error::new_spanned(
+                    args,
+                    format!(
+                        "the struct of view `{view_name}` has {expected} type and const \
+                         parameters, but {} arguments are given",
+                        args.args.len(),
+                    ),
+                ));
+            }
+            let mut args = args.args.iter();
+            gens_without_bounds
+                .iter()
+                .map(|arg| match arg {
+                    syn::GenericArgument::Lifetime(_) => arg.clone(),
+                    _ => args.next().unwrap().clone(),
+                })
+                .collect()
+        }
+    };
+    let lifetime = fresh_lifetime("'a", gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).map(|_| &lifetime);
+    // the bounds of a type alias aren't checked, the view's are, and the parameters bound by `args`
+    // are left out
+    let params = gens
+        .iter()
+        .filter(|param| args.is_none() || matches!(param, syn::GenericParam::Lifetime(_)));
+    let params = params.map(|param| match param {
+        syn::GenericParam::Lifetime(def) => {
+            let lifetime = &def.lifetime;
+            quote::quote!(#lifetime)
+        }
+        syn::GenericParam::Type(ty) => {
+            let ident = &ty.ident;
+            quote::quote!(#ident)
+        }
+        syn::GenericParam::Const(c) => {
+            let (ident, ty) = (&c.ident, &c.ty);
+            quote::quote!(const #ident: #ty)
+        }
+    });
+    let mut doc =
+        format!("The view [`{view_name}`] borrowing all its fields for the lifetime `{lifetime}`");
+    match args {
+        Some(args) => doc += &format!(", of the arguments `{}`.", quote::quote!(#args)),
+        None => doc += ".",
+    }
+
+    Ok(quote::quote! {
+        #[doc = #doc]
+        #[allow(non_camel_case_types)]
+        #vis type #alias < #lifetime, #(#params,)* > =
+            #view_name < #(#lifetimes,)* #(#view_args,)* >;
+    })
+}
+
+/// Generates `Foo::field_names`, listing the fields of the view in the order of the struct.
+fn construct_view_reflect(
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime.iter().chain(&mut_lifetime).collect::<Vec<_>>();
+    // the fields under `#[cfg]` are only listed when enabled, which a slice literal can't express,
+    // so the names are counted and filled in by a `const` block
+    let enabled =
+        fields
+            .iter()
+            .map(|field| {
+                let predicates = field.cfgs.iter().filter_map(|cfg| {
+                    match cfg.tokens.clone().into_iter().next() {
+                        Some(TokenTree::Group(group)) => Some(group.stream()),
+                        _ => None,
+                    }
+                });
+                quote::quote!(::core::cfg!(all(#(#predicates),*)))
+            })
+            .collect::<Vec<_>>();
+    let names = fields.iter().map(|field| ident_name(&field.ident));
+
+    quote::quote! {
+        impl < #(#lifetimes,)* #(#gens,)* >
+        #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            /// The names of the fields of the view, in the order of the struct.
+            pub fn field_names() -> &'static [&'static str] {
+                const LEN: usize = 0 #(+ #enabled as usize)*;
+                const NAMES: [&str; LEN] = {
+                    let mut names = [""; LEN];
+                    let mut len = 0;
+                    #(
+                        if #enabled {
+                            names[len] = #names;
+                            len += 1;
+                        }
+                    )*
+                    let _ = len;
+                    names
+                };
+                &NAMES
+            }
+        }
+    }
+}
+
+/// Implements `Default` for a view of only copied or cloned fields, defaulting each of them.
+fn construct_view_default(
+    default: &syn::Path,
+    view_name: &Ident,
+    fields: &[ViewField],
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> syn::Result<TokenStream> {
+    if let Some(field) = fields.iter().find(|field| matches!(field.share, Ref | Mut)) {
+        return Err(syn::Error::new_spanned(
+            default,
+            format!(
+                "view `{view_name}` can't derive `Default`, it borrows field `{}`",
+                field.ident
+            ),
+        ));
+    }
+    let where_clause = extend_where_clause(
+        where_clause,
+        fields.iter().filter(|field| field.cfgs.is_empty()).map(
+            |ViewField { ty, .. }| -> syn::WherePredicate {
+                syn::parse_quote_spanned!(ty.span()=> #ty: ::core::default::Default)
+            },
+        ),
+    );
+    let inits = fields
+        .iter()
+        .map(|ViewField { ident, .. }| quote::quote!(#ident: ::core::default::Default::default()))
+        .zip(fields)
+        .map(with_cfgs);
+
+    Ok(quote::quote! {
+        impl < #(#gens,)* > ::core::default::Default
+        for #view_name < #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #[inline]
+            fn default() -> Self {
+                Self {
+                    #(#inits,)*
+                    __viu_marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+    })
+}
+
+/// Appends `predicates` to a copy of `where_clause`.
+fn extend_where_clause(
+    where_clause: &Option<syn::WhereClause>,
+    predicates: impl IntoIterator<Item = syn::WherePredicate>,
+) -> syn::WhereClause {
+    let mut where_clause = where_clause.clone().unwrap_or_else(|| syn::WhereClause {
+        where_token: Default::default(),
+        predicates: Default::default(),
+    });
+    where_clause.predicates.extend(predicates);
+    where_clause
+}
+
+/// Generates the free function `foo`, borrowing the view `Foo` out of the struct like `as_foo`.
+fn construct_view_type_fn_ctor(
+    (struct_name, is_union): (&Ident, bool),
+    view_name: &Ident,
+    fields: &[ViewField],
+    vis: &syn::Visibility,
+    gens: &[syn::GenericParam],
+    gens_without_bounds: &[syn::GenericArgument],
+    where_clause: &Option<syn::WhereClause>,
+) -> TokenStream {
+    let snake_name = to_snake_case(&ident_name(view_name));
+    let fn_name = syn::Ident::new(&format!("{snake_name}_view"), view_name.span());
+    let method_name = syn::Ident::new(&format!("as_{snake_name}"), view_name.span());
+    // the struct may have lifetimes of its own, so the one of the borrow can't be elided
+    let brw_lifetime = fresh_lifetime("'__brw__", gens);
+    let (ref_lifetime, mut_lifetime) = view_lifetimes(fields, gens);
+    let lifetimes = ref_lifetime
+        .iter()
+        .chain(&mut_lifetime)
+        .map(|_| &brw_lifetime);
+    let mutability = mut_lifetime.as_ref().map(|_| quote::quote!(mut));
+    let doc = format!("Borrows the fields of view [`{view_name}`] out of `src`.");
+
+    let (unsafety, safety_doc, body) = if is_union {
+        (
+            Some(quote::quote!(unsafe)),
+            Some(quote::quote! {
+                #[doc = ""]
+                #[doc = "# Safety"]
+                #[doc = ""]
+                #[doc = "The borrowed fields of the union must hold valid values of their types."]
+            }),
+            quote::quote!(unsafe { #struct_name::#method_name(src) }),
+        )
+    } else {
+        (None, None, quote::quote!(#struct_name::#method_name(src)))
+    };
+
+    quote::quote! {
+        #[doc = #doc]
+        #safety_doc
+        #[inline]
+        #[track_caller]
+        #vis #unsafety fn #fn_name < #brw_lifetime, #(#gens,)* > (
+            src: &#brw_lifetime #mutability #struct_name < #(#gens_without_bounds,)* >,
+        ) -> #view_name < #(#lifetimes,)* #(#gens_without_bounds,)* >
+        #where_clause
+        {
+            #body
+        }
+    }
+}
+
+/// `module` is the path the view is named by, for the items generated in a `views_module`.
+fn construct_view_type_ctor(
+    view_name: &Ident,
+    fields: &[ViewField],
+    local: bool,
+    module: Option<&TokenStream>,
+) -> TokenStream {
+    let ctor_name = syn::Ident::new(&format!("{}_ctor", ident_name(view_name)), view_name.span());
+    let src = syn::Ident::new("__viu_src", Span::call_site());
+    let doc = format!(
+        "Builds the view [`{view_name}`] out of an expression with its fields. Prefer `as_{}`.",
+        to_snake_case(&ident_name(view_name))
+    );
+
+    // A variable has its fields borrowed one by one, so that views disjoint from each other
+    // can be built out of it at once. Any other `$e` is evaluated exactly once: it is matched
+    // as `__viu_src` and every field is borrowed out of that binding. The scrutinee of a
+    // `match` ending the block lives until the end of the statement, so a temporary like
+    // `make()` does too, but no further: a `let` doesn't extend the temporaries of a scrutinee,
+    // and fields borrowed out of `$e` itself would evaluate it once each. A view with mutable
+    // fields needs a `&mut` binding, which is obtained through a method call so that `$e` may be
+    // a `&mut` reference held by an immutable binding.
+    let (place_trait, scrutinee) = if fields.iter().any(|field| field.share == Mut) {
+        (
+            Some(quote::quote! {
+                trait __ViuPlace {
+                    fn __viu_place(&mut self) -> &mut Self {
+                        self
+                    }
+                }
+                impl<T: ?Sized> __ViuPlace for T {}
+            }),
+            quote::quote!(($e).__viu_place()),
+        )
+    } else {
+        (None, quote::quote!(&$e))
+    };
+
+    let var_fields = borrow_view_fields(&quote::quote!($var), fields);
+    let fields = borrow_view_fields(&quote::quote!(#src), fields);
+    let view_path = match module {
+        Some(module) => quote::quote!(#module :: #view_name),
+        None => quote::quote!(#view_name),
+    };
+
+    // a local macro is only in scope textually after the derive, re-exporting
+    // it by path lets it be `use`d from anywhere in the crate
+    let (macro_attr, reexport) = if local {
+        (
+            quote::quote!(#[allow(unused_macros)]),
+            Some(quote::quote! {
+                #[allow(unused_imports)]
+                pub(crate) use #ctor_name;
+            }),
+        )
+    } else {
+        (quote::quote!(#[macro_export]), None)
+    };
+
+    quote::quote! {
+        #[doc = #doc]
+        #macro_attr
+        macro_rules! #ctor_name {
+            ($var: ident) => {
+                #view_path {
+                    #(#var_fields,)*
+                    __viu_marker : ::core::marker::PhantomData,
+                }
+            };
+            ($e: expr) => {{
+                #place_trait
+                match #scrutinee {
+                    #src => #view_path {
+                        #(#fields,)*
+                        __viu_marker : ::core::marker::PhantomData,
+                    },
+                }
+            }};
+        }
+
+        #reexport
+    }
+}
+
+#[cfg(test)]
+mod tests;
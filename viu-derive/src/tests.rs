@@ -0,0 +1,231 @@
+//! Checks of the expansion itself, for what the compiled views can't tell.
+
+use crate::views_derive_impl;
+use proc_macro2::TokenStream;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+fn expand(input: TokenStream) -> TokenStream {
+    views_derive_impl(syn::parse2(input).unwrap(), false).unwrap()
+}
+
+/// The methods of the impls of the expansion, by name, with whether they're `#[inline]`.
+fn methods(expansion: TokenStream) -> Vec<(String, bool)> {
+    let file = syn::parse2::<syn::File>(expansion).unwrap();
+    let mut methods = Vec::new();
+    for item in file.items {
+        let syn::Item::Impl(item) = item else {
+            continue;
+        };
+        for item in item.items {
+            if let syn::ImplItem::Method(method) = item {
+                let inline = method.attrs.iter().any(|attr| attr.path.is_ident("inline"));
+                methods.push((method.sig.ident.to_string(), inline));
+            }
+        }
+    }
+    methods
+}
+
+#[test]
+fn generated_methods_are_inline() {
+    let methods = methods(expand(quote::quote! {
+        #[view_as(Shared, Exclusive)]
+        #[view_split(Shared, Exclusive)]
+        struct Point {
+            #[ref_in(Shared)]
+            x: i32,
+            #[mut_in(Exclusive)]
+            y: i32,
+        }
+    }));
+    for name in ["as_shared", "as_exclusive", "reborrow", "clone", "from"] {
+        assert!(
+            methods.iter().any(|(method, _)| method == name),
+            "no method `{name}`"
+        );
+    }
+    for (method, inline) in methods {
+        assert!(inline, "method `{method}` isn't `#[inline]`");
+    }
+}
+
+#[test]
+fn copy_impls_have_the_where_clause_of_the_view() {
+    let file = syn::parse2::<syn::File>(expand(quote::quote! {
+        #[view_as(Stamp)]
+        struct Event<T> where T: Default {
+            #[copy_in(Stamp)]
+            at: T,
+            #[ref_in(Stamp)]
+            name: String,
+        }
+    }))
+    .unwrap();
+    let where_clause = |generics: &syn::Generics| {
+        let where_clause = &generics.where_clause;
+        quote::quote!(#where_clause).to_string()
+    };
+    let view = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Struct(item) if item.ident == "Stamp" => Some(where_clause(&item.generics)),
+            _ => None,
+        })
+        .unwrap();
+    assert!(view.contains("T : :: core :: marker :: Copy"), "{view}");
+    let impls = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Impl(item) => item.trait_.as_ref().map(|(_, path, _)| (path, item)),
+            _ => None,
+        })
+        .filter(|(path, _)| {
+            let last = &path.segments.last().unwrap().ident;
+            last == "Clone" || last == "Copy"
+        })
+        .map(|(_, item)| where_clause(&item.generics))
+        .collect::<Vec<_>>();
+    assert_eq!(impls, [view.clone(), view]);
+}
+
+#[test]
+fn views_declare_the_lifetimes_of_their_fields_only() {
+    let file = syn::parse2::<syn::File>(expand(quote::quote! {
+        #[view_as(Read, Write, Both)]
+        struct Pair {
+            #[ref_in(Read, Both)]
+            a: i32,
+            #[mut_in(Write, Both)]
+            b: i32,
+        }
+    }))
+    .unwrap();
+    let lifetimes = |name: &str| {
+        file.items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Struct(item) if item.ident == name => Some(
+                    item.generics
+                        .lifetimes()
+                        .map(|param| param.lifetime.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+                _ => None,
+            })
+            .unwrap()
+    };
+    assert_eq!(lifetimes("Read"), ["'__ref__"]);
+    assert_eq!(lifetimes("Write"), ["'__mut__"]);
+    assert_eq!(lifetimes("Both"), ["'__ref__", "'__mut__"]);
+
+    // nor does anything else generated for a view use the lifetime it lacks
+    let expansion = expand(quote::quote! {
+        #[view_as(Read)]
+        struct Single {
+            #[ref_in(Read)]
+            a: i32,
+        }
+    })
+    .to_string();
+    assert!(!expansion.contains("__mut__"), "{expansion}");
+}
+
+#[test]
+fn expansion_is_deterministic() {
+    // every map of the derive is hashed with its own random keys, so that views iterated out of
+    // one in its order would come out shuffled from one expansion to the next
+    let input = quote::quote! {
+        #[view_as(A, B, C, D, E, F, G, H)]
+        #[view_derive(Debug)]
+        struct Many {
+            #[ref_in(A, C, E, G)]
+            #[mut_in(B, D, F, H)]
+            x: i32,
+            #[mut_in(A, C, E, G)]
+            #[ref_in(D, F, H)]
+            y: i32,
+        }
+    };
+    let first = expand(input.clone()).to_string();
+    for _ in 0..8 {
+        assert_eq!(expand(input.clone()).to_string(), first);
+    }
+}
+
+#[test]
+fn redundant_views_are_reported_by_the_attributes_as_spelled() {
+    let expansion = expand(quote::quote! {
+        #[view_as(Foo)]
+        struct Cache {
+            #[ref_in(Foo)]
+            #[borrow_in(Foo)]
+            entries: RefCell<u8>,
+            #[views(ref: Foo)]
+            #[views(ref: Foo)]
+            hits: u8,
+        }
+    })
+    .to_string();
+    for message in [
+        "field `entries` is put in view `Foo` by `borrow_in`, as by `ref_in` already",
+        "field `hits` is put in view `Foo` by `views` more than once",
+    ] {
+        assert!(expansion.contains(message), "no warning {message:?}");
+    }
+}
+
+/// Formats code the way the golden files are checked in.
+fn rustfmt(code: &str) -> String {
+    let mut rustfmt = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("rustfmt formats the golden files");
+    rustfmt
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(code.as_bytes())
+        .unwrap();
+    let output = rustfmt.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Compares the expansion of every struct under `tests/expand` with the one checked in next to
+/// it as `.expanded.rs`. `VIU_BLESS=1 cargo test` writes them anew, to be reviewed along with the
+/// change of the derive.
+#[test]
+fn expansions_match_the_golden_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand");
+    let mut inputs = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !path.to_string_lossy().ends_with(".expanded.rs"))
+        .collect::<Vec<_>>();
+    inputs.sort();
+    assert!(!inputs.is_empty());
+
+    for input in inputs {
+        let expansion = expand(fs::read_to_string(&input).unwrap().parse().unwrap());
+        let expansion = rustfmt(&expansion.to_string());
+        let golden = input.with_extension("expanded.rs");
+        if env::var_os("VIU_BLESS").is_some() {
+            fs::write(&golden, expansion).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&golden)
+            .unwrap_or_else(|_| panic!("no {}, bless it first", golden.display()));
+        assert!(
+            expansion == expected,
+            "the expansion of {} changed, bless it if that's intended:\n{expansion}",
+            input.display()
+        );
+    }
+}
@@ -0,0 +1,189 @@
+#[doc = "A view borrowing `data` shared."]
+#[derive(Debug)]
+#[allow(non_camel_case_types, dead_code)]
+struct Read<'__ref__, T: Clone> {
+    data: &'__ref__ Vec<T>,
+    #[doc(hidden)]
+    __viu_marker: ::core::marker::PhantomData<(&'__ref__ (), fn() -> *const T)>,
+}
+impl<'__ref__, T: Clone> ::core::clone::Clone for Read<'__ref__, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'__ref__, T: Clone> ::core::marker::Copy for Read<'__ref__, T> {}
+impl<'__ref__, T: Clone> ::viu::View for Read<'__ref__, T> {
+    type Source = Buffer<T>;
+    const NAME: &'static str = "Read";
+}
+impl<'__ref__, T: Clone> Read<'__ref__, T> {
+    #[inline]
+    pub fn reborrow<'__brw__>(&'__brw__ mut self) -> Read<'__ref__, T> {
+        Read {
+            data: self.data,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<T: Clone> Buffer<T> {
+    #[doc = "Borrows the fields of view [`Read`] out of `self`."]
+    #[inline]
+    #[track_caller]
+    fn as_read(&self) -> Read<'_, T> {
+        Read {
+            data: &self.data,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<'__ref__, T: Clone> ::core::convert::From<&'__ref__ Buffer<T>> for Read<'__ref__, T> {
+    #[inline]
+    fn from(value: &'__ref__ Buffer<T>) -> Self {
+        value.as_read()
+    }
+}
+#[doc = "Types the view [`Read`] can be borrowed out of."]
+#[allow(non_camel_case_types, dead_code)]
+trait AsRead<T: Clone> {
+    #[doc = "Borrows the fields of view [`Read`] out of `self`."]
+    fn as_read(&self) -> Read<'_, T>;
+}
+impl<T: Clone> AsRead<T> for Buffer<T> {
+    #[inline]
+    #[track_caller]
+    fn as_read(&self) -> Read<'_, T> {
+        Buffer::as_read(self)
+    }
+}
+#[doc = "Builds the view [`Read`] out of an expression with its fields. Prefer `as_read`."]
+#[macro_export]
+macro_rules! Read_ctor {
+    ($ var : ident) => {
+        Read {
+            data: &$var.data,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    };
+    ($ e : expr) => {{
+        match &$e {
+            __viu_src => Read {
+                data: &__viu_src.data,
+                __viu_marker: ::core::marker::PhantomData,
+            },
+        }
+    }};
+}
+impl<'__ref__, T: Clone> Read<'__ref__, T> {
+    #[doc = "Reborrows every field of the view shared, for as long as `self` is borrowed."]
+    #[inline]
+    pub fn as_shared(&self) -> Self {
+        *self
+    }
+}
+#[doc = "A view borrowing `data` mutably and `cursor` mutably."]
+#[derive()]
+#[allow(non_camel_case_types, dead_code)]
+struct Write<'__mut__, T: Clone> {
+    data: &'__mut__ mut Vec<T>,
+    cursor: &'__mut__ mut usize,
+    #[doc(hidden)]
+    __viu_marker: ::core::marker::PhantomData<(&'__mut__ mut (), fn() -> *const T)>,
+}
+impl<'__mut__, T: Clone> ::viu::View for Write<'__mut__, T> {
+    type Source = Buffer<T>;
+    const NAME: &'static str = "Write";
+}
+impl<'__mut__, T: Clone> Write<'__mut__, T> {
+    #[inline]
+    pub fn reborrow<'__brw__>(&'__brw__ mut self) -> Write<'__brw__, T> {
+        Write {
+            data: &mut *self.data,
+            cursor: &mut *self.cursor,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<T: Clone> Buffer<T> {
+    #[doc = "Borrows the fields of view [`Write`] out of `self`."]
+    #[inline]
+    #[track_caller]
+    fn as_write(&mut self) -> Write<'_, T> {
+        Write {
+            data: &mut self.data,
+            cursor: &mut self.cursor,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<'__mut__, T: Clone> ::core::convert::From<&'__mut__ mut Buffer<T>> for Write<'__mut__, T> {
+    #[inline]
+    fn from(value: &'__mut__ mut Buffer<T>) -> Self {
+        value.as_write()
+    }
+}
+#[doc = "Types the view [`Write`] can be borrowed out of."]
+#[allow(non_camel_case_types, dead_code)]
+trait AsWrite<T: Clone> {
+    #[doc = "Borrows the fields of view [`Write`] out of `self`."]
+    fn as_write(&mut self) -> Write<'_, T>;
+}
+impl<T: Clone> AsWrite<T> for Buffer<T> {
+    #[inline]
+    #[track_caller]
+    fn as_write(&mut self) -> Write<'_, T> {
+        Buffer::as_write(self)
+    }
+}
+#[doc = "Builds the view [`Write`] out of an expression with its fields. Prefer `as_write`."]
+#[macro_export]
+macro_rules ! Write_ctor { ($ var : ident) => { Write { data : & mut $ var . data , cursor : & mut $ var . cursor , __viu_marker : :: core :: marker :: PhantomData , } } ; ($ e : expr) => { { trait __ViuPlace { fn __viu_place (& mut self) -> & mut Self { self } } impl < T : ? Sized > __ViuPlace for T { } match ($ e) . __viu_place () { __viu_src => Write { data : & mut __viu_src . data , cursor : & mut __viu_src . cursor , __viu_marker : :: core :: marker :: PhantomData , } , } } } ; }
+#[doc = "A view borrowing `data` shared and `cursor` shared."]
+#[derive()]
+#[allow(non_camel_case_types, dead_code)]
+struct WriteRef<'__ref__, T: Clone> {
+    data: &'__ref__ Vec<T>,
+    cursor: &'__ref__ usize,
+    #[doc(hidden)]
+    __viu_marker: ::core::marker::PhantomData<(&'__ref__ (), fn() -> *const T)>,
+}
+impl<'__ref__, T: Clone> ::core::clone::Clone for WriteRef<'__ref__, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'__ref__, T: Clone> ::core::marker::Copy for WriteRef<'__ref__, T> {}
+impl<'__ref__, T: Clone> WriteRef<'__ref__, T> {
+    #[inline]
+    pub fn reborrow<'__brw__>(&'__brw__ mut self) -> WriteRef<'__ref__, T> {
+        WriteRef {
+            data: self.data,
+            cursor: self.cursor,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<'__ref__, T: Clone> ::viu::View for WriteRef<'__ref__, T> {
+    type Source = Buffer<T>;
+    const NAME: &'static str = "WriteRef";
+}
+impl<'__mut__, T: Clone> Write<'__mut__, T> {
+    #[doc = "Reborrows every field of the view shared, for as long as `self` is borrowed."]
+    #[inline]
+    pub fn as_shared(&self) -> WriteRef<'_, T> {
+        WriteRef {
+            data: &*self.data,
+            cursor: &*self.cursor,
+            __viu_marker: ::core::marker::PhantomData,
+        }
+    }
+}
+impl<'__shared__, '__mut__, T: Clone> ::core::convert::From<&'__shared__ Write<'__mut__, T>>
+    for WriteRef<'__shared__, T>
+{
+    #[inline]
+    fn from(view: &'__shared__ Write<'__mut__, T>) -> Self {
+        view.as_shared()
+    }
+}
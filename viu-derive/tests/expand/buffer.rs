@@ -0,0 +1,11 @@
+// The input of the derive, compared with its expansion checked in as `buffer.expanded.rs`: a
+// generic struct with a shared view and a mutable one.
+#[view_as(Read, Write)]
+#[view_derive(Read: Debug)]
+struct Buffer<T: Clone> {
+    #[ref_in(Read)]
+    #[mut_in(Write)]
+    data: Vec<T>,
+    #[mut_in(Write)]
+    cursor: usize,
+}
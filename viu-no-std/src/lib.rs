@@ -0,0 +1,40 @@
+//! Checks that the generated views only rely on `core`.
+#![no_std]
+
+use viu::Views;
+
+#[derive(Views)]
+#[view_as(Motion, Position, Sensor)]
+#[view_derive(Motion: PartialEq)]
+#[view_debug]
+#[view_subset(Position: from Motion)]
+#[view_split(Position, Sensor)]
+#[view_accessors(Sensor)]
+pub struct Robot<'a, T: Copy> {
+    #[mut_in(Motion, Position)]
+    pub pos: (i32, i32),
+    #[ref_in(Motion)]
+    pub speed: i32,
+    #[copy_in(Sensor)]
+    pub reading: T,
+    #[reborrow_in(Sensor)]
+    #[ref_in(Sensor)]
+    pub name: &'a str,
+}
+
+pub fn step<T: Copy>(robot: &mut Robot<'_, T>) -> T {
+    let mut motion = robot.as_motion();
+    motion.pos.0 += *motion.speed;
+    let position = motion.to_position();
+    position.pos.1 += 1;
+
+    let (position, sensor) = robot.split_position_sensor();
+    position.pos.0 += sensor.name().len() as i32;
+    let reading = sensor.reading();
+
+    let motion = robot.as_motion();
+    let shared = motion.as_shared();
+    let _ = (shared.pos, shared.speed);
+    let _: Motion<'_, '_, '_, T> = Motion_ctor!(robot);
+    reading
+}
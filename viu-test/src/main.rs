@@ -13,6 +13,14 @@ struct Fuck {
     b: String,
 }
 
+fn baz(f: &mut Fuck) {
+    *f.as_fuck_view_a().a += 1;
+    assert_eq!(f.as_fuck_view_b().b, "123");
+    let mut a_b = f.as_fuck_view_a_and_b();
+    *a_b.b += "456";
+    bar(a_b.reborrow())
+}
+
 fn foo(f: &mut Fuck) {
     *FuckViewA_ctor!(f).a += 1;
     dbg!(FuckViewB_ctor!(f).b);
@@ -21,11 +29,48 @@ fn foo(f: &mut Fuck) {
     bar(a_b.reborrow())
 }
 
+fn only_a<'a>(a: FuckViewA<'a>) -> &'a mut i32 {
+    a.a
+}
+
+fn only_b<'b>(b: FuckViewB<'b>) -> &'b String {
+    b.b
+}
+
+fn both_b(b: FuckViewB) -> usize {
+    let copied = b;
+    only_b(b).len() + only_b(copied).len()
+}
+
+// views are covariant over their lifetimes
+fn shorten<'short, 'long: 'short>(b: FuckViewB<'long>) -> FuckViewB<'short> {
+    b
+}
+
+fn shorten_mut<'short, 'long: 'short>(a: FuckViewA<'long>) -> FuckViewA<'short> {
+    a
+}
+
+fn bump_a<'a>(a: impl Into<FuckViewA<'a>>) {
+    *a.into().a += 1;
+}
+
 fn bar(mut a_b: FuckViewAAndB) {
     *FuckViewA_ctor!(a_b).a += 2;
     dbg!(FuckViewB_ctor!(a_b).b);
 }
 
+fn both(f: &mut Fuck) {
+    // a variable is borrowed field by field, so disjoint views can be held at once
+    let view_a = FuckViewA_ctor!(f);
+    let view_b = FuckViewB_ctor!(f);
+    *view_a.a += view_b.b.len() as i32;
+}
+
+fn counted<'a>(calls: &mut u32, f: &'a mut Fuck) -> &'a mut Fuck {
+    *calls += 1;
+    f
+}
 
 mod inner {
 
@@ -34,19 +79,3100 @@ mod inner {
     fn assert_expend_macro() {
         let fuck = Fuck {
             a: 0,
-            b: "".to_string()
+            b: "".to_string(),
         };
 
         FuckViewB_ctor!(fuck);
     }
 }
 
-fn main() {
-    let mut fuck = Fuck {
-        a: 0,
-        b: "".to_string()
-    };
+#[deny(warnings)]
+mod lowercase {
+    use viu::Views;
 
-    foo(&mut fuck);
-    println!("fuck {:?}", fuck.a);
+    #[derive(Views)]
+    #[view_as(lower_view)]
+    pub struct Lower {
+        #[mut_in(lower_view)]
+        pub a: i32,
+    }
+
+    pub fn bump(lower: &mut Lower) {
+        *lower_view_ctor!(lower).a += 1;
+    }
+}
+
+mod tuple {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(PairView)]
+    pub struct Pair(#[mut_in(PairView)] pub u32, #[ref_in(PairView)] pub String);
+
+    pub fn bump(pair: &mut Pair) -> usize {
+        let mut view = PairView_ctor!(pair);
+        *view.reborrow()._0 += 1;
+        view._1.len()
+    }
+}
+
+mod debug {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(NamesView)]
+    #[view_debug]
+    pub struct Names<T> {
+        #[ref_in(NamesView)]
+        pub first: T,
+        #[mut_in(NamesView)]
+        pub last: T,
+    }
+
+    pub fn check() {
+        let mut names = Names {
+            first: "a",
+            last: "b",
+        };
+        assert_eq!(
+            format!("{:?}", names.as_names_view()),
+            r#"NamesView { first: "a", last: "b" }"#
+        );
+    }
+}
+
+mod const_generic {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(RowView)]
+    pub struct Grid<T: Copy, const N: usize> {
+        #[mut_in(RowView)]
+        pub row: [T; N],
+        #[ref_in(RowView)]
+        pub len: usize,
+    }
+
+    pub fn check() {
+        let mut grid = Grid {
+            row: [0u8; 4],
+            len: 4,
+        };
+        let mut view = grid.as_row_view();
+        let view = view.reborrow();
+        view.row[*view.len - 1] = 1;
+        assert_eq!(grid.row, [0, 0, 0, 1]);
+    }
+}
+
+mod whole {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(XView)]
+    #[view_all_mut(Everything)]
+    #[view_all_ref(Snapshot)]
+    pub struct Point {
+        #[mut_in(XView)]
+        pub x: i32,
+        pub y: i32,
+    }
+
+    fn sum(snapshot: Snapshot) -> i32 {
+        *snapshot.x + *snapshot.y
+    }
+
+    pub fn check() {
+        let mut point = Point { x: 1, y: 2 };
+        *point.as_x_view().x += 1;
+        let everything = point.as_everything();
+        *everything.x += 1;
+        *everything.y += 1;
+        assert_eq!(sum(point.as_snapshot()), 6);
+    }
+}
+
+mod subset {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Big)]
+    #[view_as(Small)]
+    #[view_as(Peek)]
+    #[view_subset(Small: from Big)]
+    #[view_subset(Peek: from Big)]
+    pub struct Config {
+        #[mut_in(Big, Small)]
+        #[ref_in(Peek)]
+        pub level: u8,
+        #[ref_in(Big, Small, Peek)]
+        pub name: String,
+        pub unused: bool,
+    }
+
+    fn name_len(peek: Peek) -> usize {
+        peek.name.len()
+    }
+
+    pub fn check() {
+        let mut config = Config {
+            level: 1,
+            name: "cfg".to_string(),
+            unused: false,
+        };
+        let mut big = config.as_big();
+        let small = big.to_small();
+        *small.level += 1;
+        assert_eq!(small.name, "cfg");
+        assert_eq!(*big.to_peek().level as usize + name_len(big.to_peek()), 5);
+        assert!(!config.unused);
+    }
+}
+
+mod split {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Left, Right)]
+    #[view_split(Left, Right)]
+    pub struct Window {
+        #[mut_in(Left)]
+        pub left: Vec<u8>,
+        #[mut_in(Right)]
+        pub right: Vec<u8>,
+        #[ref_in(Left, Right)]
+        pub width: usize,
+    }
+
+    pub fn check() {
+        let mut window = Window {
+            left: vec![],
+            right: vec![],
+            width: 2,
+        };
+        let (left, right) = window.split_left_right();
+        left.left.resize(*left.width, 1);
+        right.right.extend_from_slice(&left.left[..*right.width]);
+        assert_eq!(window.right, [1, 1]);
+    }
+}
+
+mod lifetime_collision {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(TextView)]
+    pub struct Text<'__mut__, '__brw__> {
+        #[mut_in(TextView)]
+        pub text: &'__mut__ mut String,
+        #[ref_in(TextView)]
+        pub suffix: &'__brw__ str,
+    }
+
+    pub fn check() {
+        let mut string = "a".to_string();
+        let mut text = Text {
+            text: &mut string,
+            suffix: "b",
+        };
+        let mut view = text.as_text_view();
+        let view = view.reborrow();
+        view.text.push_str(view.suffix);
+        assert_eq!(string, "ab");
+    }
+}
+
+mod assoc_where {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(PeekView)]
+    #[view_split(PeekView, LastView)]
+    #[view_as(LastView)]
+    #[view_debug(LastView)]
+    #[view_subset(LastView: from PeekView)]
+    pub struct Peekable<T: Iterator>
+    where
+        T::Item: Clone + core::fmt::Debug,
+    {
+        #[mut_in(PeekView)]
+        pub iter: T,
+        #[ref_in(PeekView, LastView)]
+        pub last: Option<T::Item>,
+    }
+
+    pub fn check() {
+        let mut peekable = Peekable {
+            iter: 1..3,
+            last: Some(0),
+        };
+        let view = peekable.as_peek_view();
+        let next = view.iter.next();
+        assert_eq!(next, Some(1));
+        assert_eq!(view.to_last_view().last.clone(), Some(0));
+        let (_, last) = peekable.split_peek_view_last_view();
+        assert_eq!(format!("{last:?}"), "LastView { last: Some(0) }");
+    }
+}
+
+mod empty {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Nothing)]
+    #[view_allow_empty(Nothing)]
+    pub struct Unit {
+        pub value: u8,
+    }
+
+    pub fn check() {
+        let mut unit = Unit { value: 0 };
+        let _nothing = unit.as_nothing();
+        unit.value += 1;
+        assert_eq!(unit.value, 1);
+    }
+}
+
+mod shared {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Counter)]
+    pub struct State {
+        #[mut_in(Counter)]
+        pub count: u32,
+        #[ref_in(Counter)]
+        pub step: u32,
+    }
+
+    fn peek(counter: CounterRef) -> u32 {
+        *counter.count + *counter.step
+    }
+
+    pub fn check() {
+        let mut state = State { count: 1, step: 2 };
+        let counter = state.as_counter();
+        *counter.count += *counter.step;
+        let shared = counter.as_shared();
+        let other = counter.as_shared();
+        assert_eq!(peek(shared) + peek(other), 10);
+        *counter.count += 1;
+        assert_eq!(state.count, 4);
+    }
+}
+
+mod nested {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(InnerSub)]
+    pub struct Inner {
+        #[mut_in(InnerSub)]
+        pub hits: u32,
+        pub misses: u32,
+    }
+
+    #[derive(Views)]
+    #[view_as(OuterView)]
+    pub struct Outer {
+        #[mut_in(OuterView)]
+        #[project_in(OuterView -> InnerSub)]
+        pub inner: Inner,
+        #[ref_in(OuterView)]
+        pub weight: u32,
+    }
+
+    pub fn check() {
+        let mut outer = Outer {
+            inner: Inner { hits: 0, misses: 0 },
+            weight: 3,
+        };
+        let mut view = outer.as_outer_view();
+        let weight = *view.weight;
+        *view.inner_as_inner_sub().hits += weight;
+        assert_eq!(outer.inner.hits, 3);
+        assert_eq!(outer.inner.misses, 0);
+    }
+}
+
+mod marker_field {
+    use core::marker::PhantomData;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(TaggedView)]
+    pub struct Tagged<T> {
+        #[mut_in(TaggedView)]
+        pub value: u32,
+        #[ref_in(TaggedView)]
+        pub _marker: PhantomData<T>,
+    }
+
+    pub fn check() {
+        let mut tagged = Tagged::<String> {
+            value: 0,
+            _marker: PhantomData,
+        };
+        let view = tagged.as_tagged_view();
+        *view.value += 1;
+        let _: &PhantomData<String> = view._marker;
+        assert_eq!(tagged.value, 1);
+    }
+}
+
+#[deny(missing_docs)]
+mod docs {
+    //! Views documented with `view_doc`.
+
+    use viu::Views;
+
+    /// A document.
+    #[derive(Views)]
+    #[view_as(TitleView)]
+    #[view_doc(TitleView = "The title of a document.")]
+    #[view_doc(TitleView = "Its body isn't borrowed.")]
+    pub struct Document {
+        /// The title.
+        #[ref_in(TitleView)]
+        pub title: String,
+        /// The body.
+        pub body: String,
+    }
+
+    /// Checks the documented views.
+    pub fn check() {
+        let document = Document {
+            title: "title".to_string(),
+            body: String::new(),
+        };
+        assert_eq!(document.as_title_view().title, "title");
+        assert!(document.body.is_empty());
+    }
+}
+
+mod local_macro {
+    pub mod first {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Foo)]
+        #[view_macro(local)]
+        pub struct First {
+            #[mut_in(Foo)]
+            pub value: u8,
+        }
+    }
+
+    pub mod second {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Foo)]
+        #[view_macro(local)]
+        pub struct Second {
+            #[ref_in(Foo)]
+            pub value: u16,
+        }
+    }
+
+    fn bump(first: &mut first::First) {
+        use first::{Foo, Foo_ctor};
+
+        *Foo_ctor!(first).value += 1;
+    }
+
+    fn get(second: &second::Second) -> u16 {
+        use second::{Foo, Foo_ctor};
+
+        *Foo_ctor!(second).value
+    }
+
+    pub fn check() {
+        let mut first = first::First { value: 1 };
+        bump(&mut first);
+        assert_eq!(
+            u16::from(first.value) + get(&second::Second { value: 3 }),
+            5
+        );
+    }
+}
+
+mod defaults {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(BufView)]
+    #[view_all_ref(BufSnapshot)]
+    #[view_debug]
+    pub struct Buf<T: Copy = u8, const N: usize = 4> {
+        #[mut_in(BufView)]
+        pub data: [T; N],
+        #[ref_in(BufView)]
+        pub len: usize,
+    }
+
+    pub fn check() {
+        let mut buf: Buf = Buf {
+            data: [0; 4],
+            len: 2,
+        };
+        let view: BufView = buf.as_buf_view();
+        view.data[*view.len] = 1;
+        let snapshot: BufSnapshot = buf.as_buf_snapshot();
+        assert_eq!(
+            format!("{:?}", snapshot.as_shared()),
+            "BufSnapshot { data: [0, 0, 1, 0], len: 2 }"
+        );
+    }
+}
+
+mod grouped {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(A, B, C)]
+    pub struct Wide {
+        #[views(ref: A, B; mut: C)]
+        pub x: u8,
+        #[views(mut: A)]
+        #[ref_in(B, C)]
+        pub y: u8,
+    }
+
+    pub fn check() {
+        let mut wide = Wide { x: 1, y: 2 };
+        let a = wide.as_a();
+        *a.y += *a.x;
+        let c = wide.as_c();
+        *c.x += *c.y;
+        let b = wide.as_b();
+        assert_eq!((*b.x, *b.y), (4, 3));
+    }
+}
+
+mod reborrowed {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Writer, Reader)]
+    pub struct Cursor<'a> {
+        #[mut_in(Writer)]
+        #[ref_in(Reader)]
+        #[reborrow_in(Writer, Reader)]
+        pub buf: &'a mut [u8],
+        #[ref_in(Writer, Reader)]
+        #[reborrow_in(Reader)]
+        pub name: &'a str,
+        #[mut_in(Writer)]
+        pub pos: usize,
+    }
+
+    fn write(writer: Writer, byte: u8) {
+        writer.buf[*writer.pos] = byte;
+        *writer.pos += 1;
+        let _: &&str = writer.name;
+    }
+
+    fn read<'r>(reader: Reader<'r, '_>) -> (&'r [u8], &'r str) {
+        (reader.buf, reader.name)
+    }
+
+    pub fn check() {
+        let mut buf = [0; 2];
+        let mut cursor = Cursor {
+            buf: &mut buf,
+            name: "cursor",
+            pos: 0,
+        };
+        write(cursor.as_writer(), 1);
+        write(Writer_ctor!(cursor), 2);
+        assert_eq!(read(cursor.as_reader()), (&[1, 2][..], "cursor"));
+    }
+}
+
+mod copied {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Stats, Tick)]
+    #[view_subset(Tick: from Stats)]
+    pub struct Sim<T: Copy> {
+        #[copy_in(Stats, Tick)]
+        pub frame: u64,
+        #[ref_in(Stats)]
+        #[copy_in(Tick)]
+        pub scale: T,
+        #[mut_in(Stats)]
+        pub log: Vec<u64>,
+    }
+
+    pub fn check() {
+        let mut sim = Sim {
+            frame: 3,
+            scale: 2u64,
+            log: vec![],
+        };
+        let stats = sim.as_stats();
+        stats.log.push(stats.frame * *stats.scale);
+        let tick = stats.to_tick();
+        let shared = stats.as_shared();
+        assert_eq!((shared.log, shared.frame, *shared.scale), (&vec![6], 3, 2));
+        sim.frame += 1;
+        assert_eq!((tick.frame, tick.scale), (3, 2));
+        assert_eq!(sim.as_tick().frame, 4);
+    }
+}
+
+mod union {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Bits)]
+    #[view_derive(Bits: PartialEq)]
+    #[repr(C)]
+    pub union Word {
+        #[ref_in(Bits)]
+        pub bits: u32,
+        pub float: f32,
+    }
+
+    pub fn check() {
+        let word = Word { float: 1.0 };
+        // SAFETY: every bit pattern is a valid `u32`
+        let bits = unsafe { word.as_bits() };
+        assert_eq!(*bits.bits, 0x3f80_0000);
+        assert!(bits == unsafe { Bits_ctor!(word) });
+    }
+}
+
+mod field_vis {
+    pub mod account {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Balance)]
+        #[view_vis(Balance = priv)]
+        pub struct Account {
+            #[mut_in(Balance)]
+            pub cents: u64,
+        }
+
+        impl Balance<'_> {
+            pub fn deposit(self, cents: u64) {
+                *self.cents += cents;
+            }
+        }
+    }
+
+    pub fn check() {
+        let mut account = account::Account { cents: 1 };
+        account.as_balance().deposit(2);
+        assert_eq!(account.cents, 3);
+    }
+}
+
+mod accessors {
+    pub mod inventory {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Stock)]
+        #[view_accessors(Stock)]
+        pub struct Inventory {
+            #[mut_in(Stock)]
+            pub items: Vec<&'static str>,
+            #[ref_in(Stock)]
+            pub capacity: usize,
+            #[copy_in(Stock)]
+            pub version: u32,
+        }
+    }
+
+    pub fn check() {
+        let mut inventory = inventory::Inventory {
+            items: vec![],
+            capacity: 1,
+            version: 2,
+        };
+        let mut stock = inventory.as_stock();
+        let capacity: &usize = stock.capacity();
+        if stock.items().len() < *capacity {
+            stock.items_mut().push("apple");
+        }
+        assert_eq!(stock.version(), 2);
+        assert_eq!(stock.as_shared().items(), &["apple"]);
+        assert_eq!(inventory.items, ["apple"]);
+    }
+}
+
+mod cfg {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Settings, Volume, Theme)]
+    #[view_derive(Settings: PartialEq)]
+    #[view_debug(Settings)]
+    #[view_subset(Volume: from Settings)]
+    #[view_split(Volume, Theme)]
+    struct Config {
+        #[mut_in(Settings, Volume)]
+        volume: u8,
+        #[cfg(any())]
+        #[mut_in(Settings, Volume)]
+        missing: NotAType,
+        #[cfg(not(any()))]
+        #[ref_in(Settings, Theme)]
+        theme: &'static str,
+    }
+
+    pub fn check() {
+        let mut config = Config {
+            volume: 3,
+            theme: "dark",
+        };
+        let settings = config.as_settings();
+        *settings.volume += 1;
+        assert_eq!(*settings.theme, "dark");
+        assert_eq!(
+            format!("{settings:?}"),
+            r#"Settings { volume: 4, theme: "dark" }"#
+        );
+        let mut other = Config {
+            volume: 4,
+            theme: "dark",
+        };
+        assert!(settings == other.as_settings());
+
+        let (volume, theme) = config.split_volume_theme();
+        *volume.volume += 1;
+        assert_eq!(*theme.theme, "dark");
+        assert_eq!(config.volume, 5);
+    }
+}
+
+mod renamed {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Motion, Coords)]
+    #[view_subset(Coords: from Motion)]
+    struct Body(
+        #[mut_in(Motion as pos, Coords as xy)] (f32, f32),
+        #[views(ref: Motion as vel)] (f32, f32),
+    );
+
+    pub fn check() {
+        let mut body = Body((0.0, 0.0), (1.0, 2.0));
+        let mut motion = body.as_motion();
+        motion.pos.0 += motion.vel.0;
+        motion.to_coords().xy.1 += motion.vel.1;
+        let coords = Coords_ctor!(body);
+        assert_eq!(*coords.xy, (1.0, 2.0));
+    }
+}
+
+mod traits {
+    pub mod shapes {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Center, Area)]
+        pub struct Circle<T> {
+            #[mut_in(Center)]
+            pub center: (T, T),
+            #[ref_in(Area)]
+            pub radius: T,
+        }
+
+        #[derive(Views)]
+        #[view_as(Corner)]
+        pub struct Square {
+            #[mut_in(Corner)]
+            pub center: (i32, i32),
+        }
+    }
+
+    use shapes::{AsArea, AsCenter};
+
+    fn shift<S: AsCenter<i32>>(shape: &mut S) {
+        shape.as_center().center.0 += 1;
+    }
+
+    fn radius<T: Copy, S: AsArea<T>>(shape: &S) -> T {
+        *shape.as_area().radius
+    }
+
+    pub fn check() {
+        let mut circle = shapes::Circle {
+            center: (0, 0),
+            radius: 2,
+        };
+        shift(&mut circle);
+        assert_eq!(circle.center, (1, 0));
+        assert_eq!(radius(&circle), 2);
+
+        let mut square = shapes::Square { center: (0, 0) };
+        shapes::AsCorner::as_corner(&mut square).center.1 += 1;
+        assert_eq!(square.center, (0, 1));
+    }
+}
+
+mod fn_ctor {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Cursor, Lookahead)]
+    #[view_fn_ctor]
+    struct Reader<'a> {
+        #[ref_in(Cursor, Lookahead)]
+        #[reborrow_in(Cursor, Lookahead)]
+        input: &'a [u8],
+        #[mut_in(Cursor)]
+        #[copy_in(Lookahead)]
+        offset: usize,
+    }
+
+    fn next(cursor: Cursor<'_, '_, '_>) -> Option<u8> {
+        let byte = cursor.input.get(*cursor.offset).copied();
+        *cursor.offset += 1;
+        byte
+    }
+
+    pub fn check() {
+        let mut reader = Reader {
+            input: b"hi",
+            offset: 0,
+        };
+        assert_eq!(next(cursor_view(&mut reader)), Some(b'h'));
+        let view = lookahead_view(&reader);
+        assert_eq!(view.input[view.offset], b'i');
+    }
+}
+
+#[allow(non_camel_case_types)]
+mod raw {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(r#type, Kind)]
+    #[view_debug]
+    #[view_accessors(Kind)]
+    #[view_fn_ctor]
+    struct Token {
+        #[mut_in(r#type, Kind)]
+        r#type: u8,
+        #[ref_in(r#type)]
+        r#match: bool,
+    }
+
+    pub fn check() {
+        let mut token = Token {
+            r#type: 1,
+            r#match: true,
+        };
+        let view = token.as_type();
+        *view.r#type += 1;
+        assert!(*view.r#match);
+        assert_eq!(format!("{view:?}"), "type { type: 2, match: true }");
+        *type_view(&mut token).r#type += 1;
+        *kind_view(&mut token).type_mut() += 1;
+        assert_eq!(*type_ctor!(token).r#type, 4);
+    }
+}
+
+mod rest {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_rest_mut(Main, except(log))]
+    struct State {
+        score: u32,
+        lives: u8,
+        #[ref_in(Main)]
+        level: usize,
+        log: Vec<String>,
+    }
+
+    pub fn check() {
+        let mut state = State {
+            score: 0,
+            lives: 3,
+            level: 2,
+            log: vec![],
+        };
+        let main = state.as_main();
+        *main.score += *main.level as u32;
+        *main.lives -= 1;
+        state.log.push("hit".to_string());
+        assert_eq!((state.score, state.lives, state.log.len()), (2, 2, 1));
+    }
+}
+
+#[deny(unused_must_use)]
+mod must_use {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Sink)]
+    #[view_must_use]
+    struct Buffer {
+        #[mut_in(Sink)]
+        bytes: Vec<u8>,
+    }
+
+    pub fn check() {
+        let mut buffer = Buffer { bytes: vec![] };
+        // `buffer.as_sink();` alone would be denied
+        buffer.as_sink().bytes.push(1);
+        assert_eq!(buffer.bytes, [1]);
+    }
+}
+
+mod generated {
+    pub mod counters {
+        use viu::Views;
+
+        // the view is named by the caller, as the views of two structs can't share a name in
+        // one module
+        macro_rules! counter {
+            ($name:ident, $view:ident) => {
+                #[derive(Views)]
+                #[view_as($view)]
+                #[view_macro(local)]
+                pub struct $name {
+                    #[mut_in($view)]
+                    pub count: u32,
+                }
+            };
+        }
+
+        counter!(Clicks, ClicksView);
+        counter!(Keys, KeysView);
+    }
+
+    use counters::{ClicksView, KeysView};
+
+    pub fn check() {
+        let mut clicks = counters::Clicks { count: 0 };
+        let mut keys = counters::Keys { count: 0 };
+        *counters::ClicksView_ctor!(clicks).count += 1;
+        *counters::KeysView_ctor!(keys).count += 2;
+        assert_eq!((clicks.count, keys.count), (1, 2));
+    }
+}
+
+mod places {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Tick)]
+    #[view_macro(local)]
+    pub struct Timer {
+        #[mut_in(Tick)]
+        elapsed: u32,
+        #[ref_in(Tick)]
+        step: u32,
+    }
+
+    struct Clock {
+        timer: Timer,
+    }
+
+    struct World {
+        clock: Clock,
+        timers: [Timer; 2],
+    }
+
+    impl World {
+        fn tick(&mut self) {
+            let tick = Tick_ctor!(self.clock.timer);
+            *tick.elapsed += *tick.step;
+        }
+    }
+
+    pub fn check() {
+        let timer = |step| Timer { elapsed: 0, step };
+        let mut world = World {
+            clock: Clock { timer: timer(1) },
+            timers: [timer(2), timer(3)],
+        };
+        world.tick();
+        assert_eq!(world.clock.timer.elapsed, 1);
+
+        let tick = Tick_ctor!(world.timers[1]);
+        *tick.elapsed += *tick.step;
+        assert_eq!(world.timers[1].elapsed, 3);
+
+        let mut boxed = Box::new(timer(4));
+        let tick = Tick_ctor!(boxed);
+        *tick.elapsed += *tick.step;
+        let tick = Tick_ctor!(*boxed);
+        *tick.elapsed += *tick.step;
+        assert_eq!(boxed.elapsed, 8);
+    }
+}
+
+mod unsized_tail {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Payload, Header)]
+    #[view_debug]
+    #[view_derive(Payload: PartialEq)]
+    #[view_fn_ctor(Payload)]
+    struct Packet<T: ?Sized> {
+        #[ref_in(Header)]
+        #[copy_in(Payload)]
+        len: usize,
+        #[mut_in(Payload)]
+        data: T,
+    }
+
+    fn fill<T: ?Sized + AsMut<[u8]>>(packet: &mut Packet<T>) {
+        let payload = packet.as_payload();
+        payload.data.as_mut()[..payload.len].fill(1);
+    }
+
+    pub fn check() {
+        let packet: &mut Packet<[u8]> = &mut Packet {
+            len: 2,
+            data: [0; 3],
+        };
+        fill(packet);
+        assert_eq!(&packet.data, [1, 1, 0]);
+        assert_eq!(*packet.as_header().len, 2);
+        let payload = payload_view(packet);
+        payload.data[2] = 1;
+        assert_eq!(
+            format!("{payload:?}"),
+            "Payload { len: 2, data: [1, 1, 1] }"
+        );
+        let other: &Packet<[u8]> = &Packet {
+            len: 2,
+            data: [1, 1, 1],
+        };
+        assert!(payload == *other);
+    }
+}
+
+mod marker_trait {
+    use viu::{View, Views};
+
+    #[derive(Views)]
+    #[view_as(Health)]
+    struct Player {
+        #[mut_in(Health)]
+        hp: u32,
+    }
+
+    fn describe<V: View<Source = Player>>(_view: &V) -> String {
+        format!("{} of a player", V::NAME)
+    }
+
+    pub fn check() {
+        let mut player = Player { hp: 10 };
+        let health = player.as_health();
+        *health.hp -= 1;
+        assert_eq!(describe(&health.as_shared()), "HealthRef of a player");
+        assert_eq!(describe(&health), "Health of a player");
+        assert_eq!(player.hp, 9);
+    }
+}
+
+mod disjoint {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Physics, Render, Audio)]
+    #[views_disjoint(Render, Audio)]
+    #[view_macro(local)]
+    struct Entity {
+        #[mut_in(Physics)]
+        #[ref_in(Render, Audio)]
+        pos: (i32, i32),
+        #[mut_in(Render)]
+        sprite: u8,
+        #[mut_in(Audio)]
+        volume: u8,
+    }
+
+    pub fn check() {
+        let mut entity = Entity {
+            pos: (1, 2),
+            sprite: 0,
+            volume: 0,
+        };
+        entity.as_physics().pos.0 += 1;
+        let render = Render_ctor!(entity);
+        let audio = Audio_ctor!(entity);
+        *render.sprite += render.pos.0 as u8;
+        *audio.volume += audio.pos.1 as u8;
+        assert_eq!((entity.sprite, entity.volume), (2, 2));
+    }
+}
+
+mod builder {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Window)]
+    #[view_builder(Window)]
+    #[view_macro(local)]
+    struct Config<T> {
+        #[mut_in(Window)]
+        width: u32,
+        #[mut_in(Window)]
+        title: T,
+        #[ref_in(Window)]
+        scale: f32,
+    }
+
+    pub fn check() {
+        let mut config = Config {
+            width: 0,
+            title: "",
+            scale: 2.0,
+        };
+        let window = WindowBuilder::new(config.as_window())
+            .with_width(640)
+            .with_title("viu")
+            .done();
+        assert_eq!(*window.scale, 2.0);
+        assert_eq!((config.width, config.title), (640, "viu"));
+    }
+}
+
+mod view_marker {
+    use std::cell::Cell;
+    use viu::Views;
+
+    /// Keeps the views on the thread they're built on.
+    pub struct Local(Cell<()>);
+
+    #[derive(Views)]
+    #[view_as(Cache)]
+    #[view_marker(Cache = Local, Cache = fn(T))]
+    #[view_macro(local)]
+    struct Store<T> {
+        #[mut_in(Cache)]
+        entries: Vec<T>,
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    pub fn check() {
+        let mut store = Store { entries: vec![1] };
+        assert_send(&store);
+        store.as_cache().entries.push(2);
+        let cache = Cache_ctor!(store);
+        assert_eq!(cache.as_shared().entries, &[1, 2]);
+    }
+}
+
+#[deny(warnings)]
+mod no_views {
+    use viu::Views;
+
+    macro_rules! record {
+        ($name:ident $(, $view:ident)*) => {
+            #[derive(Views)]
+            #[view_as($($view),*)]
+            #[view_as]
+            pub struct $name {
+                $(#[ref_in($view)])*
+                pub id: u32,
+            }
+        };
+    }
+
+    record!(Plain);
+    record!(Tagged, Id);
+
+    pub fn check() {
+        let plain = Plain { id: 1 };
+        let tagged = Tagged { id: 2 };
+        assert_eq!(plain.id + *tagged.as_id().id, 3);
+    }
+}
+
+mod pair {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Pos, Vel)]
+    #[view_pair(Pos, Vel = VelRead)]
+    #[view_macro(local)]
+    struct Particle {
+        #[mut_in(Pos)]
+        pos: (f32, f32),
+        #[mut_in(Vel)]
+        vel: (f32, f32),
+    }
+
+    fn speed(vel: VelRead<'_>) -> f32 {
+        vel.vel.0.hypot(vel.vel.1)
+    }
+
+    pub fn check() {
+        let mut particle = Particle {
+            pos: (0.0, 0.0),
+            vel: (3.0, 4.0),
+        };
+        let vel = particle.as_vel();
+        vel.vel.0 *= 2.0;
+        let pos = particle.as_pos();
+        pos.pos.1 += 1.0;
+        assert_eq!(particle.as_pos_ref().pos, &(0.0, 1.0));
+        assert_eq!(speed(particle.as_vel_read()), 4.0f32.hypot(6.0));
+        assert_eq!(speed(particle.as_vel().as_shared()), 4.0f32.hypot(6.0));
+    }
+}
+
+mod where_bounds {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Handler, Label)]
+    #[view_derive(Label: PartialEq)]
+    #[view_debug(Label)]
+    #[view_subset(Label: from Handler)]
+    #[view_split(Handler, Counter)]
+    #[view_as(Counter)]
+    #[view_builder(Handler)]
+    #[view_fn_ctor(Label)]
+    #[view_macro(local)]
+    struct Callback<'a, T, F>
+    where
+        T: 'static + std::fmt::Debug + PartialEq,
+        F: for<'x> Fn(&'x u8) -> &'x u8,
+    {
+        #[mut_in(Handler)]
+        f: F,
+        #[ref_in(Handler, Label)]
+        label: &'a T,
+        #[mut_in(Counter)]
+        calls: u32,
+    }
+
+    pub fn check() {
+        let label = "id";
+        let mut callback = Callback {
+            f: (|x| x) as fn(&u8) -> &u8,
+            label: &label,
+            calls: 0,
+        };
+        let (handler, counter) = callback.split_handler_counter();
+        *counter.calls += 1;
+        assert_eq!((handler.f)(&1), &1);
+        assert_eq!(**handler.to_label().label, "id");
+        let label = callback.as_label();
+        assert!(label == callback);
+        assert!(label == label_view(&callback));
+        assert_eq!(format!("{label:?}"), r#"Label { label: "id" }"#);
+        let handler = HandlerBuilder::new(callback.as_handler())
+            .with_f(|_| &0)
+            .done();
+        assert_eq!((handler.f)(&1), &0);
+        assert_eq!(callback.calls, 1);
+    }
+}
+
+mod module {
+    pub mod shapes {
+        use viu::Views;
+
+        #[derive(Views)]
+        #[view_as(Corner, Area)]
+        #[view_accessors(Area)]
+        #[view_pair(Corner)]
+        #[view_fn_ctor(Corner)]
+        #[view_macro(local)]
+        #[views_module(crate::module::shapes::views)]
+        pub struct Rect {
+            #[mut_in(Corner)]
+            #[ref_in(Area)]
+            pub x: i32,
+            #[ref_in(Area)]
+            w: i32,
+        }
+
+        impl Rect {
+            pub fn new() -> Self {
+                Rect { x: 1, w: 2 }
+            }
+
+            pub fn area(&self) -> i32 {
+                let area: views::Area = self.as_area();
+                *area.x() * *area.w()
+            }
+        }
+
+        pub fn move_to(corner: views::Corner, x: i32) {
+            *corner.x = x;
+        }
+    }
+
+    use shapes::views::{AsCorner, Corner_ctor};
+    use shapes::views::{Corner, CornerRef};
+
+    mod unimported {
+        // the macro names the view by its path out of `$crate`, so it needn't be imported
+        pub fn corner_x(rect: &mut super::shapes::Rect) -> i32 {
+            *crate::module::shapes::views::Corner_ctor!(rect).x
+        }
+    }
+
+    pub fn check() {
+        let mut rect = shapes::Rect::new();
+        assert_eq!(rect.area(), 2);
+        shapes::move_to(rect.as_corner(), 3);
+        let corner: CornerRef = rect.as_corner_ref();
+        assert_eq!(*corner.x, 3);
+        let corner: Corner = shapes::views::corner_view(&mut rect);
+        *corner.x += 1;
+        assert_eq!(*AsCorner::as_corner(&mut rect).x, 4);
+        assert_eq!(*Corner_ctor!(rect).x, 4);
+        assert_eq!(unimported::corner_x(&mut rect), 4);
+        assert_eq!(rect.as_area().x(), &4);
+    }
+}
+
+mod split_group {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as()]
+    #[split_group(Ends: 0, 2)]
+    struct Body<T: ?Sized>(Vec<u8>, u8, T);
+
+    #[derive(Views)]
+    #[view_as(Unused)]
+    #[view_allow_empty]
+    #[split_group(Motion: pos, vel)]
+    struct Particle {
+        pos: f32,
+        vel: f32,
+        mass: f32,
+    }
+
+    pub fn check() {
+        let mut particle = Particle {
+            pos: 0.0,
+            vel: 2.0,
+            mass: 1.0,
+        };
+        let (pos, vel) = particle.split_motion();
+        *pos += *vel;
+        assert_eq!(particle.pos, 2.0);
+        assert_eq!(particle.mass, 1.0);
+
+        let body: &mut Body<[u8]> = &mut Body(vec![1], 0, [2]);
+        let (head, tail) = body.split_ends();
+        head.push(tail[0]);
+        assert_eq!(body.0, [1, 2]);
+        assert_eq!(body.1, 0);
+    }
+}
+
+mod declared {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Summary(ref: name, score; mut: seen), Scores(mut: score))]
+    #[view_as(Full)]
+    #[view_macro(local)]
+    struct Entry {
+        #[ref_in(Full)]
+        name: String,
+        #[ref_in(Summary, Full)]
+        score: u32,
+        seen: bool,
+    }
+
+    #[derive(Views)]
+    #[view_as(Pair(ref: 0; mut: 1))]
+    #[view_macro(local)]
+    struct Both(u8, u8);
+
+    pub fn check() {
+        let mut entry = Entry {
+            name: "a".to_owned(),
+            score: 1,
+            seen: false,
+        };
+        *entry.as_scores().score += 1;
+        let summary = entry.as_summary();
+        *summary.seen = true;
+        assert_eq!((summary.name.as_str(), *summary.score), ("a", 2));
+        assert!(entry.seen);
+        assert_eq!(
+            (entry.as_full().name.as_str(), *entry.as_full().score),
+            ("a", 2)
+        );
+
+        let mut both = Both(1, 2);
+        let pair = both.as_pair();
+        *pair._1 += *pair._0;
+        assert_eq!(both.1, 3);
+    }
+}
+
+#[deny(warnings)]
+mod asserted {
+    use std::cell::Cell;
+    use viu::Views;
+
+    // `&mut Cell<u32>` is `Send` where `&Cell<u32>` isn't, so `count` is `mut_in` for all it's a
+    // `Cell`, which the derive would warn about
+    #[derive(Views)]
+    #[view_as(Shared, Counter)]
+    #[view_assert(Send, Unpin)]
+    #[view_assert(Shared: Sync)]
+    #[view_macro(local)]
+    #[allow(deprecated)]
+    struct Worker<'a, T: ?Sized + Sync> {
+        #[ref_in(Shared)]
+        name: &'a str,
+        #[mut_in(Counter)]
+        count: Cell<u32>,
+        #[ref_in(Shared)]
+        data: T,
+    }
+
+    pub fn check() {
+        let mut worker = Worker {
+            name: "w",
+            count: Cell::new(0),
+            data: *b"ab",
+        };
+        let counter = worker.as_counter();
+        counter.count.set(1);
+        std::thread::scope(|scope| {
+            let shared = worker.as_shared();
+            scope.spawn(move || assert_eq!((*shared.name, shared.data), ("w", b"ab")));
+        });
+        assert_eq!(worker.count.get(), 1);
+    }
+}
+
+mod transposed {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Settings, Locked)]
+    #[view_accessors(Locked)]
+    #[view_macro(local)]
+    struct Config<'a> {
+        #[ref_in(Settings, Locked, transpose)]
+        title: Option<String>,
+        #[ref_in(Settings, transpose)]
+        #[reborrow_in(Settings)]
+        parent: &'a Option<u32>,
+        #[mut_in(Settings)]
+        retries: u8,
+    }
+
+    pub fn check() {
+        let mut config = Config {
+            title: Some("a".to_owned()),
+            parent: &None,
+            retries: 0,
+        };
+        let settings = config.as_settings();
+        *settings.retries += 1;
+        assert_eq!(settings.title(), Some(&"a".to_owned()));
+        assert_eq!(settings.title, &Some("a".to_owned()));
+        assert_eq!(settings.parent(), None);
+        let locked = config.as_locked();
+        let title: Option<&String> = locked.title();
+        assert_eq!(title.map(String::as_str), Some("a"));
+        assert_eq!(config.retries, 1);
+    }
+}
+
+mod non_exhaustive {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Header)]
+    #[view_non_exhaustive(Header)]
+    #[view_macro(local)]
+    pub struct Packet {
+        #[mut_in(Header)]
+        pub len: u16,
+        #[ref_in(Header)]
+        pub kind: u8,
+        pub body: Vec<u8>,
+    }
+
+    pub fn check() {
+        let mut packet = Packet {
+            len: 0,
+            kind: 1,
+            body: vec![0; 4],
+        };
+        let Header { len, kind, .. } = packet.as_header();
+        *len = 4;
+        assert_eq!(*kind, 1);
+        let header = Header_ctor!(packet);
+        let HeaderRef { len, .. } = header.as_shared();
+        assert_eq!(*len as usize, packet.body.len());
+    }
+}
+
+mod repr {
+    use std::mem::{align_of, size_of};
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Raw, Single, Aligned)]
+    #[view_repr(Raw = C, Single = transparent)]
+    #[view_repr(Aligned = C, Aligned = align(16))]
+    #[view_macro(local)]
+    struct Buffer {
+        #[mut_in(Raw, Single, Aligned)]
+        ptr: *mut u8,
+        #[copy_in(Raw, Aligned)]
+        len: usize,
+    }
+
+    pub fn check() {
+        let mut buffer = Buffer {
+            ptr: std::ptr::null_mut(),
+            len: 3,
+        };
+        let raw = buffer.as_raw();
+        assert!(raw.ptr.is_null());
+        assert_eq!(raw.len, 3);
+        assert_eq!(size_of::<Raw>(), 2 * size_of::<usize>());
+        assert_eq!(size_of::<Single>(), size_of::<&mut *mut u8>());
+        assert_eq!(align_of::<Aligned>(), 16);
+        let _ = buffer.as_single();
+        let _ = buffer.as_aligned();
+    }
+}
+
+mod reborrow_name {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Cursor, Snapshot)]
+    #[view_reborrow(Cursor = reborrow_cursor, Snapshot = _)]
+    #[view_macro(local)]
+    struct Editor {
+        #[mut_in(Cursor)]
+        pos: usize,
+        #[ref_in(Cursor, Snapshot)]
+        text: String,
+    }
+
+    impl Cursor<'_, '_> {
+        fn reborrow(&mut self) -> &mut usize {
+            self.pos
+        }
+    }
+
+    trait Reborrow {
+        fn reborrow(&self) -> &str;
+    }
+
+    impl Reborrow for Snapshot<'_> {
+        fn reborrow(&self) -> &str {
+            self.text
+        }
+    }
+
+    pub fn check() {
+        let mut editor = Editor {
+            pos: 0,
+            text: "abc".to_owned(),
+        };
+        let mut cursor = editor.as_cursor();
+        *cursor.reborrow_cursor().pos += 1;
+        *cursor.reborrow() += 1;
+        assert_eq!(cursor.as_shared().reborrow_cursor().text, "abc");
+        assert_eq!(editor.pos, 2);
+        assert_eq!(editor.as_snapshot().reborrow(), "abc");
+    }
+}
+
+mod indexed {
+    use std::collections::HashMap;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Cells, Names, Lens)]
+    #[view_index(Cells, Names, Lens)]
+    #[view_macro(local)]
+    struct Sheet<T> {
+        #[mut_in(Cells)]
+        cells: Vec<T>,
+        #[ref_in(Names)]
+        names: HashMap<String, usize>,
+        #[copy_in(Lens)]
+        lens: [u8; 2],
+    }
+
+    pub fn check() {
+        let mut sheet = Sheet {
+            cells: vec![1, 2, 3],
+            names: HashMap::from([("a".to_owned(), 0)]),
+            lens: [4, 5],
+        };
+        let mut cells = sheet.as_cells();
+        cells[1] += 10;
+        assert_eq!(&cells[1..], [12, 3]);
+        assert_eq!(sheet.as_names()["a"], 0);
+        assert_eq!(sheet.as_lens()[1], 5);
+        assert_eq!(sheet.cells, [1, 12, 3]);
+    }
+}
+
+mod cloned {
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Handle, Session, Owned)]
+    #[view_subset(Owned: from Session)]
+    #[view_derive(Owned: Clone, Debug)]
+    #[view_macro(local)]
+    struct Server {
+        #[clone_in(Handle, Session, Owned)]
+        config: Arc<String>,
+        #[clone_in(Session, Owned)]
+        cache: Rc<Vec<u8>>,
+        #[mut_in(Session)]
+        hits: u32,
+    }
+
+    fn keep(config: &Server) -> Owned {
+        config.as_owned()
+    }
+
+    pub fn check() {
+        let mut server = Server {
+            config: Arc::new("conf".to_owned()),
+            cache: Rc::new(vec![1]),
+            hits: 0,
+        };
+        let handle: Handle = server.as_handle();
+        let session = server.as_session();
+        *session.hits += 1;
+        assert_eq!(**session.as_shared().cache, [1]);
+        let owned = session.to_owned();
+        drop(server);
+        assert_eq!(handle.config.as_str(), "conf");
+        assert_eq!(Arc::strong_count(&handle.config), 3);
+        assert_eq!(owned.clone().cache.len(), 1);
+
+        let server = Server {
+            config: Arc::new("other".to_owned()),
+            cache: Rc::new(Vec::new()),
+            hits: 0,
+        };
+        let owned = keep(&server);
+        assert_eq!(Rc::strong_count(&owned.cache), 2);
+        assert_eq!(owned.config.as_str(), "other");
+    }
+}
+
+mod foreign {
+    use std::ops::Range;
+
+    viu::view! {
+        #[view_derive(Debug, PartialEq)]
+        #[view_fn_ctor]
+        #[view_macro(local)]
+        Range<T> where T: PartialOrd => pub Bounds { mut start: T; ref end: T }
+    }
+
+    viu::view! {
+        #[view_macro(local)]
+        Range<T> => pub End { ref end: T }
+    }
+
+    fn clamp<T: AsBounds<u32>>(range: &mut T) {
+        let bounds = range.as_bounds();
+        if *bounds.start > *bounds.end {
+            *bounds.start = *bounds.end;
+        }
+    }
+
+    pub fn check() {
+        let mut range = Range { start: 5, end: 3 };
+        clamp(&mut range);
+        assert_eq!(range, 3..3);
+        let bounds = bounds_view(&mut range);
+        *bounds.start = 1;
+        assert!(format!("{bounds:?}").starts_with("Bounds { start: 1, end: 3"));
+        let mut other = 1..3;
+        assert!(bounds == other.as_bounds());
+        let end: End<u32> = (&range).into();
+        assert_eq!(*end.end, 3);
+        assert_eq!(*Bounds_ctor!(range).start, 1);
+    }
+}
+
+mod forwarded {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Listed, Bare)]
+    #[view_forward_attrs(Listed: allow)]
+    #[view_derive(Debug)]
+    #[view_macro(local)]
+    struct Account {
+        #[ref_in(Listed, Bare)]
+        #[allow(clippy::struct_field_names)]
+        account_id: u32,
+        #[mut_in(Listed)]
+        #[allow(unused)]
+        balance: i64,
+    }
+
+    #[derive(Views)]
+    #[view_as(Everything)]
+    #[view_forward_attrs]
+    #[view_macro(local)]
+    struct Ledger {
+        #[ref_in(Everything)]
+        #[allow(dead_code)]
+        #[allow(clippy::all)]
+        entries: Vec<i64>,
+    }
+
+    pub fn check() {
+        let mut account = Account {
+            account_id: 7,
+            balance: 10,
+        };
+        let listed = account.as_listed();
+        *listed.balance += 5;
+        assert_eq!(*listed.account_id, 7);
+        assert_eq!(*account.as_bare().account_id, 7);
+        assert_eq!(account.balance, 15);
+        let ledger = Ledger {
+            entries: vec![1, -2],
+        };
+        assert_eq!(ledger.as_everything().entries.len(), 2);
+    }
+}
+
+mod temporary {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Label, Counter)]
+    #[view_macro(local)]
+    struct Widget {
+        #[ref_in(Label)]
+        label: String,
+        #[mut_in(Counter)]
+        count: u32,
+    }
+
+    fn make() -> Widget {
+        Widget {
+            label: "ok".to_owned(),
+            count: 1,
+        }
+    }
+
+    fn bump(counter: Counter) -> u32 {
+        *counter.count += 1;
+        *counter.count
+    }
+
+    pub fn check() {
+        // the temporaries live until the end of the statement
+        let label = Label_ctor!(make()).label.clone();
+        assert_eq!(label, "ok");
+        let count = bump(Counter_ctor!(make()));
+        assert_eq!(count, 2);
+        let len = Label_ctor!(make()).label.len() + *Counter_ctor!(make()).count as usize;
+        assert_eq!(len, 3);
+        // a view kept past the statement is built out of a binding of the temporary
+        let mut widget = make();
+        let counter = Counter_ctor!(widget);
+        *counter.count += 1;
+        assert_eq!(widget.count, 2);
+    }
+}
+
+mod borrowed_through {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Read, Write, Nested)]
+    #[view_borrow_through(Read, Write)]
+    #[view_macro(local)]
+    struct Frame<'a, T> {
+        #[ref_in(Read, Nested)]
+        name: &'a str,
+        #[ref_in(Read)]
+        #[mut_in(Write)]
+        pixels: &'a mut [T],
+        #[ref_in(Read)]
+        #[mut_in(Write)]
+        palette: &'a Vec<T>,
+        #[mut_in(Write)]
+        width: usize,
+    }
+
+    fn first<'r, T: Copy>(read: Read<'r, '_, T>) -> (&'r str, T, usize) {
+        let name: &str = read.name;
+        let pixels: &[T] = read.pixels;
+        let palette: &Vec<T> = read.palette;
+        (name, pixels[0], palette.len())
+    }
+
+    pub fn check() {
+        let mut pixels = [1, 2, 3];
+        let palette = vec![7];
+        let mut frame = Frame {
+            name: "frame",
+            pixels: &mut pixels,
+            palette: &palette,
+            width: 3,
+        };
+        assert_eq!(first(frame.as_read()), ("frame", 1, 1));
+        let write = frame.as_write();
+        let pixels: &mut [i32] = write.pixels;
+        pixels[0] = 4;
+        // a shared reference can't be reborrowed mutably, it stays as it is
+        let palette: &mut &Vec<i32> = write.palette;
+        assert_eq!(palette[0], 7);
+        *write.width -= 1;
+        // views not opted in still borrow the reference itself
+        let name: &&str = frame.as_nested().name;
+        assert_eq!(*name, "frame");
+        assert_eq!(frame.width, 2);
+        assert_eq!(pixels_of(frame), [4, 2, 3]);
+    }
+
+    fn pixels_of<'a, T>(frame: Frame<'a, T>) -> &'a [T] {
+        frame.pixels
+    }
+}
+
+mod defaulted {
+    use std::rc::Rc;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Snapshot)]
+    #[view_derive(Snapshot: Default, Debug)]
+    #[view_macro(local)]
+    struct Player<T> {
+        #[copy_in(Snapshot)]
+        hp: u32,
+        #[clone_in(Snapshot)]
+        items: Rc<Vec<T>>,
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    pub fn check() {
+        let initial = Snapshot::<String>::default();
+        assert_eq!(initial.hp, 0);
+        assert!(initial.items.is_empty());
+        let player = Player {
+            hp: 3,
+            items: Rc::new(vec!["sword".to_owned()]),
+            name: "p".to_owned(),
+        };
+        let mut state = player.as_snapshot();
+        assert_eq!(state.items.len(), 1);
+        state = Default::default();
+        assert_eq!(state.hp, 0);
+        assert_eq!(player.hp, 3);
+    }
+}
+
+mod narrowed {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Physics, Motion, Mass)]
+    #[view_narrow(Physics => Motion)]
+    #[view_narrow(Physics => Mass)]
+    #[view_macro(local)]
+    struct Body<T> {
+        #[mut_in(Physics, Motion)]
+        pos: T,
+        #[ref_in(Physics, Motion)]
+        vel: T,
+        #[mut_in(Physics)]
+        #[ref_in(Mass)]
+        mass: f32,
+    }
+
+    fn step(motion: Motion<f32>) {
+        *motion.pos += *motion.vel;
+    }
+
+    fn weigh(mass: Mass<f32>) -> f32 {
+        *mass.mass
+    }
+
+    pub fn check() {
+        let mut body = Body {
+            pos: 1.0,
+            vel: 2.0,
+            mass: 3.0,
+        };
+        let mut physics = body.as_physics();
+        step(physics.narrow_to_motion());
+        step((&mut physics).into());
+        assert_eq!(weigh((&physics).into()), 3.0);
+        *physics.mass = 4.0;
+        assert_eq!(weigh(physics.narrow_to_mass()), 4.0);
+        assert_eq!(body.pos, 5.0);
+    }
+}
+
+mod sole {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Input)]
+    #[view_macro(local)]
+    struct Form {
+        #[mut_in]
+        text: String,
+        #[ref_in]
+        limit: usize,
+        #[copy_in]
+        focused: bool,
+        #[allow(dead_code)]
+        id: u32,
+    }
+
+    pub fn check() {
+        let mut form = Form {
+            text: "ab".to_owned(),
+            limit: 3,
+            focused: true,
+            id: 0,
+        };
+        let input = form.as_input();
+        if input.focused && input.text.len() < *input.limit {
+            input.text.push('c');
+        }
+        assert_eq!(form.text, "abc");
+    }
+}
+
+mod wrapped {
+    use std::cell::Cell;
+    use std::ops::{Deref, DerefMut};
+    use viu::Views;
+
+    thread_local! {
+        static WRITES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Counts the mutable accesses to the borrowed value.
+    pub struct Tracked<R>(R);
+
+    impl<'a, T: ?Sized> Tracked<&'a mut T> {
+        pub fn new(borrow: &'a mut T) -> Self {
+            Self(borrow)
+        }
+    }
+
+    impl<T: ?Sized> Deref for Tracked<&mut T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.0
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for Tracked<&mut T> {
+        fn deref_mut(&mut self) -> &mut T {
+            WRITES.with(|writes| writes.set(writes.get() + 1));
+            self.0
+        }
+    }
+
+    #[derive(Views)]
+    #[view_as(Edit, Count, Plain)]
+    #[view_wrap(Edit = Tracked, Count = self::Tracked)]
+    #[view_subset(Count: from Edit)]
+    #[view_derive(Edit: PartialEq)]
+    #[view_debug(Edit)]
+    #[view_accessors(Count)]
+    #[view_macro(local)]
+    struct Doc {
+        #[mut_in(Edit, Plain)]
+        #[ref_in(Count)]
+        text: String,
+        #[mut_in(Edit, Count, Plain)]
+        edits: u32,
+    }
+
+    fn edit(mut edit: Edit) {
+        edit.text.push('!');
+        *edit.edits += 1;
+        let mut count = edit.to_count();
+        *count.edits_mut() += 1;
+        assert_eq!(*count.edits(), 2);
+        assert_eq!(count.text(), "hi!");
+    }
+
+    pub fn check() {
+        let mut doc = Doc {
+            text: "hi".to_owned(),
+            edits: 0,
+        };
+        edit(doc.as_edit());
+        // reborrowing a wrapped field mutably counts too
+        assert_eq!(WRITES.with(Cell::get), 4);
+        let mut other = Doc {
+            text: "hi!".to_owned(),
+            edits: 2,
+        };
+        assert!(doc.as_edit() == other.as_edit());
+        assert!(format!("{:?}", doc.as_edit()).contains("\"hi!\""));
+        let plain = Plain_ctor!(doc);
+        *plain.edits += plain.text.len() as u32;
+        assert_eq!(WRITES.with(Cell::get), 4);
+        let mut edit = Edit_ctor!(other);
+        // a reborrow reborrows every field
+        *edit.reborrow().edits += 1;
+        assert_eq!(WRITES.with(Cell::get), 7);
+        assert_eq!((doc.edits, other.edits), (5, 3));
+    }
+}
+
+mod restricted {
+    pub mod outer {
+        pub mod inner {
+            use viu::Views;
+
+            #[derive(Views)]
+            #[view_as(Scoped, Parent)]
+            #[view_vis(Parent = pub(super))]
+            #[view_fn_ctor]
+            pub(in crate::restricted) struct Config {
+                #[ref_in(Scoped)]
+                pub(in crate::restricted) name: String,
+                #[mut_in(Scoped, Parent)]
+                pub(super) level: u8,
+                #[ref_in(Parent)]
+                pub(self) secret: u8,
+            }
+
+            #[derive(Views)]
+            #[view_as(Nested)]
+            #[views_module(nested_views)]
+            #[view_accessors(Nested)]
+            pub(in crate::restricted::outer) struct Limits {
+                #[mut_in(Nested)]
+                pub(super) max: u32,
+                #[ref_in(Nested)]
+                pub(self) min: u32,
+                #[ref_in(Nested)]
+                pub(in crate::restricted) step: u32,
+            }
+
+            pub(in crate::restricted) fn config() -> Config {
+                Config {
+                    name: "n".to_owned(),
+                    level: 1,
+                    secret: 2,
+                }
+            }
+
+            pub(super) fn limits() -> Limits {
+                Limits {
+                    max: 3,
+                    min: 1,
+                    step: 1,
+                }
+            }
+
+            pub(super) fn secret(config: &mut Config) -> u8 {
+                *config.as_parent().secret
+            }
+
+            pub(super) fn lowest(limits: &mut Limits) -> u32 {
+                *limits.as_nested().min()
+            }
+        }
+
+        pub(super) fn raise(config: &mut inner::Config) -> u8 {
+            let secret = inner::secret(config);
+            *config.as_parent().level += secret;
+            *inner::scoped_view(config).level
+        }
+
+        pub(super) fn widen() -> u32 {
+            let mut limits = inner::limits();
+            let mut nested = limits.as_nested();
+            let step = *nested.step();
+            *nested.max_mut() += step;
+            let lowest = inner::lowest(&mut limits);
+            limits.max + lowest
+        }
+    }
+
+    pub fn check() {
+        let mut config = outer::inner::config();
+        assert_eq!(*outer::inner::scoped_view(&mut config).name, "n");
+        assert_eq!(outer::raise(&mut config), 3);
+        assert_eq!(outer::widen(), 5);
+    }
+}
+
+mod named {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Read, Write)]
+    #[view_naming(suffix = "View")]
+    #[view_subset(Read: from Write)]
+    #[view_derive(Read: Debug)]
+    #[view_macro(local)]
+    struct File {
+        #[ref_in(Read)]
+        #[mut_in(Write)]
+        data: Vec<u8>,
+        #[mut_in(Write)]
+        cursor: usize,
+    }
+
+    fn write(view: &mut impl AsWriteView, byte: u8) {
+        let write = view.as_write_view();
+        write.data.push(byte);
+        *write.cursor += 1;
+    }
+
+    pub fn check() {
+        let mut file = File {
+            data: Vec::new(),
+            cursor: 0,
+        };
+        write(&mut file, 1);
+        let view: WriteView = WriteView_ctor!(file);
+        let read: ReadView = view.to_read_view();
+        assert_eq!(read.data, &[1]);
+        let shared: WriteViewRef = view.as_shared();
+        assert_eq!(*shared.cursor, 1);
+        assert!(format!("{:?}", file.as_read_view()).starts_with("ReadView"));
+    }
+}
+
+mod parser {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Lexer, Peek)]
+    #[view_borrow_through(Peek)]
+    #[view_macro(local)]
+    struct Parser<'src> {
+        #[mut_in(Lexer)]
+        #[ref_in(Peek)]
+        pos: usize,
+        #[copy_in(Lexer)]
+        #[ref_in(Peek)]
+        input: &'src str,
+        #[allow(dead_code)]
+        depth: u32,
+    }
+
+    /// The tokens outlive the lexer, as they borrow the input rather than the view.
+    fn next_word<'src>(lexer: Lexer<'_, 'src>) -> Option<&'src str> {
+        let rest = &lexer.input[*lexer.pos..];
+        let start = rest.len() - rest.trim_start().len();
+        let len = rest[start..].find(' ').unwrap_or(rest.len() - start);
+        *lexer.pos += start + len;
+        (len > 0).then(|| &rest[start..start + len])
+    }
+
+    fn peek(peek: Peek) -> Option<char> {
+        let input: &str = peek.input;
+        input[*peek.pos..].chars().find(|c| !c.is_whitespace())
+    }
+
+    pub fn check() {
+        let source = String::from("let x = 1");
+        // the words outlive the parser
+        let words = {
+            let mut parser = Parser {
+                pos: 0,
+                input: &source,
+                depth: 0,
+            };
+            let mut words = Vec::new();
+            while let Some(word) = next_word(parser.as_lexer()) {
+                words.push(word);
+                if peek(parser.as_peek()) == Some('=') {
+                    parser.depth += 1;
+                }
+            }
+            assert_eq!(parser.depth, 1);
+            words
+        };
+        assert_eq!(words, ["let", "x", "=", "1"]);
+    }
+}
+
+mod reflected {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Stats, Empty)]
+    #[view_reflect]
+    #[view_allow_empty(Empty)]
+    #[view_macro(local)]
+    struct Monster<T> {
+        #[ref_in(Stats)]
+        name: T,
+        #[cfg(any())]
+        #[ref_in(Stats)]
+        hidden: u8,
+        #[mut_in(Stats as health)]
+        hp: u32,
+        #[cfg(not(any()))]
+        #[copy_in(Stats)]
+        r#type: u8,
+    }
+
+    pub fn check() {
+        assert_eq!(Stats::<String>::field_names(), ["name", "health", "type"]);
+        assert!(Empty::<String>::field_names().is_empty());
+        let mut monster = Monster {
+            name: "orc",
+            hp: 3,
+            r#type: 1,
+        };
+        let stats = monster.as_stats();
+        *stats.health -= 1;
+        assert_eq!((*stats.name, stats.r#type), ("orc", 1));
+    }
+}
+
+mod trailing {
+    use viu::Views;
+
+    // rustfmt would drop the trailing commas this checks
+    #[rustfmt::skip]
+    #[derive(Views)]
+    #[view_as(Pos, Vel(mut: vel,), Motion,)]
+    #[view_rest_mut(Rest, except(id,),)]
+    #[view_subset(Pos: from Motion,)]
+    #[view_split(Pos, Vel,)]
+    #[view_debug(Pos,)]
+    #[view_macro(local,)]
+    struct Particle {
+        #[views(ref: Pos,; mut: Motion,)]
+        pos: (i32, i32),
+        #[mut_in(Motion,)]
+        vel: (i32, i32),
+        #[ref_in(Motion,)]
+        id: i32,
+    }
+
+    pub fn check() {
+        let mut particle = Particle {
+            pos: (0, 0),
+            vel: (1, 2),
+            id: 7,
+        };
+        let motion = particle.as_motion();
+        motion.pos.0 += motion.vel.0;
+        motion.vel.1 = *motion.id;
+        assert_eq!(motion.to_pos().pos, &(1, 0));
+        let (pos, vel) = particle.split_pos_vel();
+        assert_eq!((pos.pos, vel.vel), (&(1, 0), &mut (1, 7)));
+        let rest = particle.as_rest();
+        rest.pos.1 = 1;
+        *rest.vel = (0, 0);
+        assert_eq!((particle.pos, particle.vel), ((1, 1), (0, 0)));
+    }
+}
+
+mod enumerated {
+    use viu::{View, Views};
+
+    #[derive(Views)]
+    #[view_as(Size, Label)]
+    #[view_derive(Size: Debug, PartialEq)]
+    enum Shape<'a, T> {
+        Circle {
+            #[ref_in(Size, Label)]
+            radius: f32,
+            #[ref_in(Label)]
+            name: &'a str,
+        },
+        Rect(#[ref_in(Size)] f32, u8, #[ref_in(Size)] f32),
+        Points(Vec<T>),
+        #[cfg(any())]
+        Hidden(#[ref_in(Size)] f32),
+    }
+
+    pub fn check() {
+        let rect: Shape<u8> = Shape::Rect(1.0, 2, 3.0);
+        assert_eq!(rect.as_size(), Size::Rect(&1.0, &3.0));
+        let area = match rect.as_size() {
+            Size::Circle { radius } => 3.0 * radius * radius,
+            Size::Rect(width, height) => width * height,
+            Size::Points => 0.0,
+        };
+        assert_eq!(area, 3.0);
+        if let Shape::Rect(_, sides, _) = rect {
+            assert_eq!(sides, 2);
+        }
+
+        let circle: Shape<u8> = Shape::Circle {
+            radius: 2.0,
+            name: "c",
+        };
+        match Label::from(&circle) {
+            Label::Circle { radius, name } => assert_eq!((*radius, *name), (2.0, "c")),
+            Label::Rect | Label::Points => unreachable!(),
+        }
+        let points = Shape::Points(vec![1, 2]);
+        assert!(matches!(points.as_label(), Label::Points));
+        if let Shape::Points(points) = points {
+            assert_eq!(points.len(), 2);
+        }
+        assert_eq!(<Size<u8> as View>::NAME, "Size");
+    }
+}
+
+mod aliased {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Step, Peek)]
+    #[view_alias(Step = StepIn, Peek = PeekIn)]
+    #[view_alias(Step<u8, 2> = ByteStepIn)]
+    #[view_macro(local)]
+    struct Cursor<'src, T: Copy, const N: usize> {
+        #[ref_in(Step, Peek)]
+        input: &'src [T; N],
+        #[mut_in(Step)]
+        pos: usize,
+    }
+
+    fn advance<'a, T: Copy, const N: usize>(step: StepIn<'a, 'a, T, N>) -> Option<T> {
+        let item = step.input.get(*step.pos).copied();
+        *step.pos += 1;
+        item
+    }
+
+    fn first<'s, T: Copy, const N: usize>(peek: PeekIn<'_, 's, T, N>) -> Option<&'s T> {
+        peek.input.first()
+    }
+
+    fn skip_byte(step: ByteStepIn<'_, '_>) -> bool {
+        *step.pos += 1;
+        *step.pos < step.input.len()
+    }
+
+    pub fn check() {
+        let mut cursor = Cursor {
+            input: &[1, 2, 3],
+            pos: 0,
+        };
+        assert_eq!(advance(cursor.as_step()), Some(1));
+        assert_eq!(advance(cursor.as_step()), Some(2));
+        assert_eq!(first(cursor.as_peek()), Some(&1));
+        assert_eq!(cursor.pos, 2);
+
+        let mut bytes = Cursor {
+            input: b"ab",
+            pos: 0,
+        };
+        assert!(skip_byte(bytes.as_step()));
+        assert!(!skip_byte(bytes.as_step()));
+    }
+}
+
+mod cells {
+    use std::cell::RefCell;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Log, Stats)]
+    #[view_accessors(Stats)]
+    #[view_macro(local)]
+    struct Service {
+        #[borrow_in(Log)]
+        lines: RefCell<Vec<String>>,
+        #[ref_in(Log)]
+        name: String,
+        #[borrow_in(Stats)]
+        hits: RefCell<u32>,
+    }
+
+    pub fn check() {
+        let service = Service {
+            lines: RefCell::new(Vec::new()),
+            name: "svc".to_string(),
+            hits: RefCell::new(0),
+        };
+        let log = service.as_log();
+        log.lines_mut().push(format!("{} up", log.name));
+        let stats = service.as_stats();
+        *stats.hits_mut() += 1;
+        *stats.hits_mut() += 1;
+        assert_eq!(*stats.hits().borrow(), 2);
+        assert_eq!(service.lines.borrow()[..], ["svc up"]);
+        let lines = log.lines_mut();
+        assert!(service.as_log().lines.try_borrow_mut().is_err());
+        drop(lines);
+    }
+}
+
+mod shared_from {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Account)]
+    #[view_macro(local)]
+    struct Bank {
+        #[mut_in(Account)]
+        balance: i64,
+        #[ref_in(Account)]
+        limit: i64,
+    }
+
+    // defined once on the companion, for both views
+    impl AccountRef<'_> {
+        fn available(self) -> i64 {
+            self.balance + self.limit
+        }
+    }
+
+    fn report(account: AccountRef<'_>) -> String {
+        format!("{} of {}", account.balance, account.available())
+    }
+
+    pub fn check() {
+        let mut bank = Bank {
+            balance: 10,
+            limit: 5,
+        };
+        let account = bank.as_account();
+        *account.balance -= 3;
+        assert_eq!(report((&account).into()), "7 of 12");
+        *account.balance -= 7;
+        assert_eq!(AccountRef::from(&account).available(), 5);
+        assert_eq!(account.as_shared().available(), 5);
+    }
+}
+
+mod delegated {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Physics, Label)]
+    #[view_delegate(Physics: fn step(&mut self, dt: i32) -> i32)]
+    #[view_delegate(Label: fn label(&self) -> &str, fn shout(&mut self, mut times: usize) -> String)]
+    #[view_macro(local)]
+    struct Body {
+        #[mut_in(Physics)]
+        pos: i32,
+        #[ref_in(Physics)]
+        vel: i32,
+        #[ref_in(Label)]
+        name: String,
+    }
+
+    // the methods are written once, on the views
+    impl Physics<'_, '_> {
+        fn step(self, dt: i32) -> i32 {
+            *self.pos += *self.vel * dt;
+            *self.pos
+        }
+    }
+
+    impl<'a> Label<'a> {
+        fn label(self) -> &'a str {
+            self.name
+        }
+
+        fn shout(self, mut times: usize) -> String {
+            let mut shout = String::new();
+            while times > 0 {
+                shout += &self.name.to_uppercase();
+                times -= 1;
+            }
+            shout
+        }
+    }
+
+    pub fn check() {
+        let mut body = Body {
+            pos: 0,
+            vel: 2,
+            name: "ball".to_string(),
+        };
+        assert_eq!(body.step(3), 6);
+        assert_eq!(body.as_physics().step(1), 8);
+        assert_eq!(body.label(), "ball");
+        assert_eq!(body.shout(2), "BALLBALL");
+    }
+}
+
+#[deny(dead_code)]
+mod unread {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Read, Write, Unused)]
+    #[view_builder(Write)]
+    #[view_macro(local)]
+    pub(crate) struct Config {
+        #[ref_in(Read, Unused)]
+        #[mut_in(Write)]
+        name: String,
+        #[ref_in(Read)]
+        #[mut_in(Write)]
+        level: u8,
+    }
+
+    #[derive(Views)]
+    #[view_as(Kind)]
+    pub(crate) enum Mode {
+        Fast(#[ref_in(Kind)] u8),
+    }
+
+    // the views are built, but none of their fields is read
+    pub fn check() {
+        let mut config = Config {
+            name: String::new(),
+            level: 0,
+        };
+        let _ = config.as_read();
+        let _ = config.as_write();
+        let Mode::Fast(speed) = Mode::Fast(1);
+        assert_eq!(speed, 1);
+        let Config { name, level } = config;
+        assert_eq!((name.len(), level), (0, 0));
+    }
+}
+
+mod conditional {
+    // `all()` holds and `any()` doesn't, like an enabled and a disabled feature
+    #[cfg_attr(all(), derive(viu::Views))]
+    #[cfg_attr(all(), view_as(Moving, Still))]
+    #[cfg_attr(all(), view_macro(local))]
+    #[cfg_attr(any(), view_debug)]
+    struct Particle {
+        #[cfg_attr(all(), mut_in(Moving))]
+        #[cfg_attr(any(), mut_in(Still))]
+        #[ref_in(Still)]
+        pos: i32,
+        #[cfg_attr(all(), ref_in(Moving), ref_in(Still))]
+        vel: i32,
+        #[cfg_attr(any(), ref_in(Moving))]
+        mass: i32,
+    }
+
+    #[cfg_attr(any(), derive(viu::Views))]
+    #[cfg_attr(any(), view_as(Missing))]
+    struct Plain {
+        #[cfg_attr(any(), ref_in(Missing))]
+        value: i32,
+    }
+
+    #[derive(viu::Views)]
+    #[view_as(Base)]
+    #[cfg_attr(feature = "extra-view", view_as(Extra))]
+    #[view_macro(local)]
+    struct Sensor {
+        #[ref_in(Base)]
+        #[cfg_attr(feature = "extra-view", ref_in(Extra))]
+        id: u32,
+    }
+
+    // a view named by `cfg_attr` only exists along with it
+    trait NoView {
+        fn as_missing(&self) -> i32;
+    }
+
+    impl NoView for Plain {
+        fn as_missing(&self) -> i32 {
+            self.value
+        }
+    }
+
+    pub fn check() {
+        let mut particle = Particle {
+            pos: 0,
+            vel: 2,
+            mass: 1,
+        };
+        let moving = particle.as_moving();
+        *moving.pos += *moving.vel;
+        let still = particle.as_still();
+        assert_eq!((*still.pos, *still.vel, particle.mass), (2, 2, 1));
+        assert_eq!(Plain { value: 3 }.as_missing(), 3);
+
+        let sensor = Sensor { id: 7 };
+        #[cfg(feature = "extra-view")]
+        assert_eq!(*sensor.as_extra().id, 7);
+        assert_eq!(*sensor.as_base().id, 7);
+    }
+}
+
+mod ref_split {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Edit)]
+    #[ref_split(Source: text, spans)]
+    #[ref_split(Spans: spans)]
+    #[split_group(Buffer: text, cursor)]
+    #[view_macro(local)]
+    struct Editor {
+        #[mut_in(Edit)]
+        text: String,
+        #[mut_in(Edit)]
+        cursor: usize,
+        spans: Vec<(usize, usize)>,
+    }
+
+    fn count(text: &str, spans: &[(usize, usize)]) -> usize {
+        spans
+            .iter()
+            .filter(|(start, _)| *start < text.len())
+            .count()
+    }
+
+    pub fn check() {
+        let mut editor = Editor {
+            text: "hello".to_string(),
+            cursor: 0,
+            spans: vec![(0, 2), (9, 10)],
+        };
+        let (text, spans) = editor.split_ref_source();
+        // the borrows coexist, along with further shared borrows of the struct
+        let (again,) = editor.split_ref_spans();
+        assert_eq!((count(text, spans), again.len(), editor.cursor), (1, 2, 0));
+        let (text, cursor) = editor.split_buffer();
+        text.push('!');
+        *cursor = text.len();
+        let edit = editor.as_edit();
+        assert_eq!((edit.text.as_str(), *edit.cursor), ("hello!", 6));
+    }
+}
+
+mod retyped {
+    use std::fmt::Write;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Output, Bytes)]
+    #[view_macro(local)]
+    struct Writer {
+        #[mut_in(Output as &mut dyn Write)]
+        text: String,
+        #[views(ref: Output as data: &[u8]; mut: Bytes as &mut [u8])]
+        buf: Vec<u8>,
+    }
+
+    fn emit(output: Output<'_, '_>) {
+        write!(output.text, "{} bytes", output.data.len()).unwrap();
+    }
+
+    pub fn check() {
+        let mut writer = Writer {
+            text: String::new(),
+            buf: vec![1, 2, 3],
+        };
+        emit(writer.as_output());
+        let bytes = writer.as_bytes();
+        bytes.buf.reverse();
+        assert_eq!(
+            (writer.text.as_str(), &writer.buf[..]),
+            ("3 bytes", &[3, 2, 1][..])
+        );
+        let output = Output_ctor!(writer);
+        assert_eq!(output.data, [3, 2, 1]);
+    }
+}
+
+mod core_trait {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Loudness)]
+    struct Speaker {
+        #[copy_in(Loudness)]
+        level: u8,
+    }
+
+    // the derive names `::viu::View`, which must be the trait of `viu-core`
+    fn view_name<V: viu_core::View>(_view: &V) -> &'static str {
+        V::NAME
+    }
+
+    pub fn check() {
+        let speaker = Speaker { level: 3 };
+        let loudness = speaker.as_loudness();
+        assert_eq!(loudness.level, 3);
+        assert_eq!(view_name(&loudness), "Loudness");
+    }
+}
+
+mod caller {
+    use std::cell::Cell;
+    use std::ops::{Deref, DerefMut};
+    use std::panic;
+    use std::sync::Mutex;
+    use viu::Views;
+
+    thread_local! {
+        static FROZEN: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Refuses to borrow or write the value while frozen, reporting where it was attempted.
+    pub struct Frozen<R>(R);
+
+    impl<'a, T: ?Sized> Frozen<&'a mut T> {
+        #[track_caller]
+        pub fn new(borrow: &'a mut T) -> Self {
+            assert!(!FROZEN.with(Cell::get), "borrowed while frozen");
+            Self(borrow)
+        }
+    }
+
+    impl<T: ?Sized> Deref for Frozen<&mut T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.0
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for Frozen<&mut T> {
+        #[track_caller]
+        fn deref_mut(&mut self) -> &mut T {
+            assert!(!FROZEN.with(Cell::get), "written while frozen");
+            self.0
+        }
+    }
+
+    #[derive(Views)]
+    #[view_as(Slots)]
+    #[view_wrap(Slots = Frozen)]
+    #[view_builder(Slots)]
+    #[view_macro(local)]
+    struct Pool {
+        #[mut_in(Slots)]
+        slots: Vec<u8>,
+    }
+
+    static LINE: Mutex<Option<u32>> = Mutex::new(None);
+
+    // the line of the panic of `f`, which should be the one calling the generated method
+    fn panic_line(f: impl FnOnce() + panic::UnwindSafe) -> Option<u32> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|info| {
+            *LINE.lock().unwrap() = info.location().map(|location| location.line());
+        }));
+        let result = panic::catch_unwind(f);
+        panic::set_hook(hook);
+        FROZEN.with(|frozen| frozen.set(false));
+        assert!(result.is_err());
+        LINE.lock().unwrap().take()
+    }
+
+    pub fn check() {
+        let line = panic_line(|| {
+            let mut pool = Pool { slots: vec![] };
+            FROZEN.with(|frozen| frozen.set(true));
+            pool.as_slots();
+        });
+        assert_eq!(line, Some(line!() - 2));
+
+        let line = panic_line(|| {
+            let mut pool = Pool { slots: vec![] };
+            let builder = SlotsBuilder::new(pool.as_slots());
+            FROZEN.with(|frozen| frozen.set(true));
+            builder.with_slots(vec![1]);
+        });
+        assert_eq!(line, Some(line!() - 2));
+    }
+}
+
+mod recursive {
+    use viu::Views;
+
+    pub trait Node<G: ?Sized> {
+        fn weight(&self, graph: &G) -> u32;
+    }
+
+    #[derive(Views)]
+    #[view_as(Nodes, Meta)]
+    #[view_derive(Meta: PartialEq)]
+    #[view_debug]
+    #[view_macro(local)]
+    struct Graph<N: Node<Graph<N>>>
+    where
+        N: Node<Graph<N>>,
+    {
+        #[mut_in(Nodes)]
+        nodes: Vec<N>,
+        #[ref_in(Meta)]
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Leaf(u32);
+
+    impl Node<Graph<Leaf>> for Leaf {
+        fn weight(&self, graph: &Graph<Leaf>) -> u32 {
+            self.0 * graph.nodes.len() as u32
+        }
+    }
+
+    // `Self` names the list in its fields and bounds, not the views
+    #[derive(Debug, Views)]
+    #[view_as(Links)]
+    #[view_debug]
+    #[view_macro(local)]
+    struct List<T>
+    where
+        T: Node<Self>,
+    {
+        #[mut_in(Links)]
+        next: Option<Box<Self>>,
+        #[ref_in(Links)]
+        item: T,
+    }
+
+    #[derive(Debug)]
+    struct Unit;
+
+    impl<G: ?Sized> Node<G> for Unit {
+        fn weight(&self, _graph: &G) -> u32 {
+            1
+        }
+    }
+
+    pub fn check() {
+        let mut graph = Graph {
+            nodes: vec![Leaf(2)],
+            name: "g".to_owned(),
+        };
+        graph.as_nodes().nodes.push(Leaf(3));
+        let meta = graph.as_meta();
+        assert_eq!(meta, graph.as_meta());
+        assert_eq!(meta.name, "g");
+        assert_eq!(graph.nodes[1].weight(&graph), 6);
+
+        let mut list = List {
+            next: None,
+            item: Unit,
+        };
+        let links = list.as_links();
+        *links.next = Some(Box::new(List {
+            next: None,
+            item: Unit,
+        }));
+        assert_eq!(links.item.weight(&()), 1);
+        assert!(format!("{links:?}").contains("next: Some"));
+        assert!(list.next.is_some());
+    }
+}
+
+mod hashed {
+    use std::collections::HashSet;
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Key, Uses)]
+    #[view_derive(Key: PartialEq, Eq, Hash)]
+    #[view_macro(local)]
+    struct Symbol<T> {
+        #[ref_in(Key)]
+        name: String,
+        #[copy_in(Key)]
+        scope: u32,
+        #[mut_in(Uses)]
+        uses: Vec<T>,
+    }
+
+    pub fn check() {
+        let symbols = [("x", 0), ("y", 0), ("x", 0), ("x", 1)].map(|(name, scope)| Symbol {
+            name: name.to_owned(),
+            scope,
+            uses: vec![()],
+        });
+        let interned = symbols.iter().map(Symbol::as_key).collect::<HashSet<_>>();
+        assert_eq!(interned.len(), 3);
+        assert!(interned.contains(&symbols[2].as_key()));
+        assert!(!interned.contains(
+            &Symbol {
+                name: "y".to_owned(),
+                scope: 1,
+                uses: vec![()],
+            }
+            .as_key()
+        ));
+    }
+}
+
+mod lenses {
+    use viu::{Getter, Lens, Views};
+
+    #[derive(Views)]
+    #[view_as(Motion, Label)]
+    #[view_lens(Motion, Label)]
+    #[view_macro(local)]
+    struct Player<'n, T: Copy> {
+        #[mut_in(Motion)]
+        pos: (i32, i32),
+        #[ref_in(Motion)]
+        #[copy_in(Label)]
+        speed: T,
+        #[reborrow_in(Label)]
+        #[ref_in(Label as title)]
+        name: &'n str,
+    }
+
+    #[derive(Views)]
+    #[view_as(Count)]
+    #[view_lens]
+    #[view_macro(local)]
+    struct Counter(#[mut_in(Count)] u32, String);
+
+    const MOTION: MotionLens<'static, i32> = MotionLens::new();
+
+    // the lenses only borrow what they are given, so a lens can be handed out and applied later
+    fn bump<S>(lens: Lens<S, u32>, source: &mut S) -> u32 {
+        *lens.get_mut(source) += 1;
+        *lens.getter().get(source)
+    }
+
+    fn read<S, T: Copy>(getter: Getter<S, T>, source: &S) -> T {
+        *getter.get(source)
+    }
+
+    pub fn check() {
+        let mut player = Player {
+            pos: (0, 0),
+            speed: 2,
+            name: "p1",
+        };
+        MOTION.pos.set(&mut player, (1, 1));
+        MOTION.pos.get_mut(&mut player).0 += read(MOTION.speed, &player);
+        let label = LabelLens::new();
+        assert_eq!(label.title.get(&player), "p1");
+        assert_eq!(player.pos, (3, 1));
+
+        let mut counter = Counter(0, "clicks".to_owned());
+        let count = CountLens::new();
+        assert_eq!(bump(count._0, &mut counter), 1);
+        assert_eq!(bump(count._0, &mut counter), 2);
+        assert_eq!((counter.0, counter.1.as_str()), (2, "clicks"));
+    }
+}
+
+mod derived {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_derive(Debug)]
+    #[view_derive(Label: Clone, PartialEq)]
+    #[view_as(Label, Entry)]
+    #[view_macro(local)]
+    struct Item {
+        #[ref_in(Label)]
+        #[mut_in(Entry)]
+        name: String,
+        #[mut_in(Entry)]
+        count: u32,
+    }
+
+    pub fn check() {
+        let mut item = Item {
+            name: "pen".to_string(),
+            count: 2,
+        };
+        assert_eq!(item.as_label(), item.as_label().clone());
+        assert!(item.as_label() == item);
+        *item.as_entry().count += 1;
+        assert!(format!("{:?}", item.as_entry()).starts_with("Entry { name: \"pen\", count: 3, "));
+    }
+}
+
+mod qualified {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Cursor)]
+    #[view_macro(local)]
+    #[views_module(views)]
+    struct Reader {
+        #[mut_in(Cursor)]
+        pos: usize,
+        #[ref_in(Cursor)]
+        data: Vec<u8>,
+    }
+
+    pub fn check() {
+        let mut reader = Reader {
+            pos: 0,
+            data: vec![1, 2],
+        };
+        // the macro names the view `views::Cursor`, so only the module needs to be in scope
+        let cursor = views::Cursor_ctor!(reader);
+        *cursor.pos += cursor.data.len();
+        assert_eq!(reader.pos, 2);
+    }
+}
+
+// the derive leaves its warnings out for a struct allowing them, these would be denied otherwise
+#[deny(warnings)]
+mod allowed {
+    use viu::Views;
+
+    #[derive(Views)]
+    #[view_as(Totals)]
+    #[view_macro(local)]
+    #[allow(deprecated)]
+    struct Tally {
+        #[ref_in(Totals)]
+        #[ref_in(Totals)]
+        hits: u32,
+    }
+
+    pub fn check() {
+        let tally = Tally { hits: 3 };
+        assert_eq!(*tally.as_totals().hits, 3);
+    }
+}
+
+fn main() {
+    let mut fuck = Fuck {
+        a: 0,
+        b: "".to_string(),
+    };
+
+    foo(&mut fuck);
+    println!("fuck {:?}", fuck.a);
+
+    baz(&mut fuck);
+    assert_eq!(fuck.a, 6);
+    assert_eq!(fuck.b, "123456");
+
+    let mut calls = 0;
+    let a_b = FuckViewAAndB_ctor!(counted(&mut calls, &mut fuck));
+    *a_b.a += 1;
+    *a_b.b += "789";
+    assert_eq!(calls, 1);
+    assert_eq!(fuck.a, 7);
+
+    *only_a(fuck.as_fuck_view_a()) += 1;
+    assert_eq!(fuck.a, 8);
+    assert_eq!(only_b(fuck.as_fuck_view_b()), "123456789");
+    assert_eq!(both_b(fuck.as_fuck_view_b()), 18);
+    assert_eq!(shorten(fuck.as_fuck_view_b()).b.len(), 9);
+    *shorten_mut(fuck.as_fuck_view_a()).a -= 1;
+    bump_a(&mut fuck);
+    let b: FuckViewB = (&fuck).into();
+    assert_eq!(b.b, "123456789");
+
+    let mut disjoint = Fuck {
+        a: 0,
+        b: "ab".to_string(),
+    };
+    both(&mut disjoint);
+    assert_eq!(disjoint.a, 2);
+
+    let mut lower = lowercase::Lower { a: 0 };
+    lowercase::bump(&mut lower);
+    assert_eq!(lower.a, 1);
+
+    let mut pair = tuple::Pair(0, "pair".to_string());
+    assert_eq!(tuple::bump(&mut pair), 4);
+    assert_eq!(pair.0, 1);
+
+    debug::check();
+    const_generic::check();
+    whole::check();
+    subset::check();
+    split::check();
+    lifetime_collision::check();
+    assoc_where::check();
+    empty::check();
+    shared::check();
+    nested::check();
+    marker_field::check();
+    docs::check();
+    local_macro::check();
+    defaults::check();
+    grouped::check();
+    reborrowed::check();
+    copied::check();
+    union::check();
+    field_vis::check();
+    accessors::check();
+    cfg::check();
+    renamed::check();
+    traits::check();
+    fn_ctor::check();
+    raw::check();
+    rest::check();
+    must_use::check();
+    generated::check();
+    places::check();
+    unsized_tail::check();
+    marker_trait::check();
+    disjoint::check();
+    builder::check();
+    view_marker::check();
+    no_views::check();
+    pair::check();
+    where_bounds::check();
+    module::check();
+    split_group::check();
+    declared::check();
+    asserted::check();
+    transposed::check();
+    non_exhaustive::check();
+    repr::check();
+    reborrow_name::check();
+    indexed::check();
+    cloned::check();
+    foreign::check();
+    forwarded::check();
+    temporary::check();
+    borrowed_through::check();
+    defaulted::check();
+    narrowed::check();
+    sole::check();
+    wrapped::check();
+    restricted::check();
+    named::check();
+    parser::check();
+    reflected::check();
+    trailing::check();
+    enumerated::check();
+    aliased::check();
+    cells::check();
+    shared_from::check();
+    delegated::check();
+    unread::check();
+    conditional::check();
+    ref_split::check();
+    retyped::check();
+    core_trait::check();
+    caller::check();
+    recursive::check();
+    hashed::check();
+    lenses::check();
+    derived::check();
+    qualified::check();
+    allowed::check();
 }
@@ -0,0 +1,144 @@
+//! Uses of the derive it must refuse, checked as `compile_fail` doctests.
+
+/// A field can't be both shared and borrowed mutably by one view.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as(Foo)]
+/// struct S {
+///     #[ref_in(Foo)]
+///     #[mut_in(Foo)]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct RefAndMutIn;
+
+/// A view marked by `view_must_use` can't be dropped unused.
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as(Foo)]
+/// #[view_must_use]
+/// struct S {
+///     #[mut_in(Foo)]
+///     a: i32,
+/// }
+///
+/// fn main() {
+///     let mut s = S { a: 0 };
+///     s.as_foo();
+/// }
+/// ```
+pub struct UnusedMustUse;
+
+/// `view_as` takes its views in parentheses, not as a string.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as = "Foo"]
+/// struct S {
+///     #[ref_in(Foo)]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct ViewAsNameValue;
+
+/// `view_as` followed by a bare name is refused by rustc, before the derive runs.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as Foo]
+/// struct S {
+///     #[ref_in(Foo)]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct ViewAsBare;
+
+/// `ref_in` takes its views in parentheses, not as a string.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as(Foo)]
+/// struct S {
+///     #[ref_in = "Foo"]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct RefInNameValue;
+
+/// `mut_in` followed by a bare name is refused by rustc, before the derive runs.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as(Foo)]
+/// struct S {
+///     #[mut_in Foo]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct MutInBare;
+
+/// The views of `mut_in` are named by identifiers.
+///
+/// ```compile_fail
+/// use viu::Views;
+///
+/// #[derive(Views)]
+/// #[view_as(Foo)]
+/// struct S {
+///     #[mut_in("Foo")]
+///     a: i32,
+/// }
+///
+/// fn main() {}
+/// ```
+pub struct MutInLiteral;
+
+/// The items of a view are named after the view alone, so two structs expanded from one
+/// `macro_rules!` can't have views of the same name in one module.
+///
+/// ```compile_fail,E0428
+/// use viu::Views;
+///
+/// macro_rules! counter {
+///     ($name:ident) => {
+///         #[derive(Views)]
+///         #[view_as(Count)]
+///         #[view_macro(local)]
+///         pub struct $name {
+///             #[mut_in(Count)]
+///             pub count: u32,
+///         }
+///     };
+/// }
+///
+/// counter!(Clicks);
+/// counter!(Keys);
+///
+/// fn main() {}
+/// ```
+pub struct SameViewInOneModule;